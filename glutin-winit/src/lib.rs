@@ -12,11 +12,20 @@ mod event_loop;
 mod window;
 
 use event_loop::GlutinEventLoop;
-pub use window::GlWindow;
+pub use window::{
+    physical_size_to_nonzero, BootstrapReport, BootstrapStep, GlWindow, ResumableGlWindow,
+    SimpleGlWindow,
+};
 
 use std::error::Error;
+#[cfg(glx_backend)]
+use std::sync::Arc;
+#[cfg(glx_backend)]
+use std::{ffi, fmt};
 
-use glutin::config::{Config, ConfigTemplateBuilder};
+#[cfg(glx_backend)]
+use glutin::api::glx::XlibErrorHookRegistrar;
+use glutin::config::{Config, ConfigTemplate, ConfigTemplateBuilder};
 use glutin::display::{Display, DisplayApiPreference};
 #[cfg(x11_platform)]
 use glutin::platform::x11::X11GlConfigExt;
@@ -25,7 +34,7 @@ use glutin::prelude::*;
 #[cfg(wgl_backend)]
 use raw_window_handle::HasWindowHandle;
 
-use raw_window_handle::RawWindowHandle;
+use raw_window_handle::{HandleError, RawDisplayHandle, RawWindowHandle};
 use winit::error::OsError;
 use winit::window::{Window, WindowAttributes};
 
@@ -59,6 +68,9 @@ pub(crate) mod private {
 pub struct DisplayBuilder {
     preference: ApiPreference,
     window_attributes: Option<WindowAttributes>,
+    strict: bool,
+    #[cfg(glx_backend)]
+    glx_error_hook: Option<GlxErrorHook>,
 }
 
 impl DisplayBuilder {
@@ -81,6 +93,43 @@ impl DisplayBuilder {
         self
     }
 
+    /// Fail the [`Self::build`] when the window can't be created as requested
+    /// instead of silently adjusting it.
+    ///
+    /// By default the transparency of the window is disabled when the picked
+    /// config doesn't support it. In the strict mode an error is returned
+    /// instead. The strict mode of the context is controlled separately with
+    /// [`ContextAttributesBuilder::with_strict`].
+    ///
+    /// The default value for this flag is `false`.
+    ///
+    /// [`ContextAttributesBuilder::with_strict`]: glutin::context::ContextAttributesBuilder::with_strict
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// The registrar of the GLX error hook, for the applications installing
+    /// their own Xlib error handler.
+    ///
+    /// The registrar is given the glutin's hook, which must be called from the
+    /// application's error handler. The hook returns `false` for the errors
+    /// not caused by GLX, which should be handled by the application then.
+    ///
+    /// By default the hook is registered with winit's
+    /// [`register_xlib_error_hook`].
+    #[cfg(glx_backend)]
+    pub fn with_glx_error_hook<F>(mut self, registrar: F) -> Self
+    where
+        F: Fn(Box<dyn Fn(*mut ffi::c_void, *mut ffi::c_void) -> bool + Send + Sync>)
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.glx_error_hook = Some(GlxErrorHook(Arc::new(registrar)));
+        self
+    }
+
     /// Initialize the OpenGL platform and create a compatible window to use
     /// with it when the [`WindowAttributes`] was passed with
     /// [`Self::with_window_attributes()`]. It's optional, since on some
@@ -95,7 +144,7 @@ impl DisplayBuilder {
     /// [`Self::with_window_attributes()`] if modern OpenGL(ES) is desired,
     /// otherwise only builtin functions like `glClear` will be available.
     pub fn build<Picker>(
-        mut self,
+        self,
         event_loop: &impl GlutinEventLoop,
         template_builder: ConfigTemplateBuilder,
         config_picker: Picker,
@@ -103,6 +152,38 @@ impl DisplayBuilder {
     where
         Picker: FnOnce(Box<dyn Iterator<Item = Config> + '_>) -> Config,
     {
+        self.build_with_template(event_loop, template_builder, |configs, _| config_picker(configs))
+    }
+
+    /// The same as [`Self::build`], but the `config_picker` is also given the
+    /// [`ConfigTemplate`] the configs were found with, so it could prefer the
+    /// configs closest to the requested one.
+    ///
+    /// ```no_run
+    /// # use glutin::config::ConfigTemplateBuilder;
+    /// # use glutin::prelude::*;
+    /// # use glutin_winit::DisplayBuilder;
+    /// # let event_loop: winit::event_loop::EventLoop<()> = unimplemented!();
+    /// let template = ConfigTemplateBuilder::new().with_alpha_size(8);
+    /// let (window, config) = DisplayBuilder::new()
+    ///     .build_with_template(&event_loop, template, |configs, template| {
+    ///         configs
+    ///             .min_by_key(|config| config.alpha_size().abs_diff(template.alpha_size()))
+    ///             .unwrap()
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn build_with_template<Picker>(
+        mut self,
+        event_loop: &impl GlutinEventLoop,
+        template_builder: ConfigTemplateBuilder,
+        config_picker: Picker,
+    ) -> Result<(Option<Window>, Config), Box<dyn Error>>
+    where
+        Picker: FnOnce(Box<dyn Iterator<Item = Config> + '_>, &ConfigTemplate) -> Config,
+    {
+        let transparent = self.window_attributes.as_ref().is_some_and(|wa| wa.transparent);
+
         // XXX with WGL backend window should be created first.
         #[cfg(wgl_backend)]
         let window = if let Some(wa) = self.window_attributes.take() {
@@ -119,7 +200,13 @@ impl DisplayBuilder {
         #[cfg(not(wgl_backend))]
         let raw_window_handle = None;
 
-        let gl_display = create_display(event_loop, self.preference, raw_window_handle)?;
+        let gl_display = create_display(
+            event_loop,
+            self.preference,
+            raw_window_handle,
+            #[cfg(glx_backend)]
+            self.glx_error_hook.as_ref(),
+        )?;
 
         // XXX the native window must be passed to config picker when WGL is used
         // otherwise very limited OpenGL features will be supported.
@@ -133,10 +220,14 @@ impl DisplayBuilder {
         let template = template_builder.build();
 
         let gl_config = unsafe {
-            let configs = gl_display.find_configs(template)?;
-            config_picker(configs)
+            let configs = gl_display.find_configs(template.clone())?;
+            config_picker(configs, &template)
         };
 
+        if self.strict && transparent && gl_config.supports_transparency() == Some(false) {
+            return Err("the picked config doesn't support the window transparency".into());
+        }
+
         #[cfg(not(wgl_backend))]
         let window = if let Some(wa) = self.window_attributes.take() {
             Some(finalize_window(event_loop, wa, &gl_config)?)
@@ -148,16 +239,52 @@ impl DisplayBuilder {
     }
 }
 
+/// The shared registrar of the GLX error hook, so the [`DisplayBuilder`] could
+/// be cloned and sent across threads.
+#[cfg(glx_backend)]
+#[derive(Clone)]
+struct GlxErrorHook(Arc<dyn Fn(XlibErrorHook) + Send + Sync>);
+
+/// The hook passed to the registrar of [`GlxErrorHook`].
+#[cfg(glx_backend)]
+type XlibErrorHook = Box<dyn Fn(*mut ffi::c_void, *mut ffi::c_void) -> bool + Send + Sync>;
+
+#[cfg(glx_backend)]
+impl fmt::Debug for GlxErrorHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlxErrorHook").finish_non_exhaustive()
+    }
+}
+
+// The builder is `Send` and `Sync`, so it could be prepared off the event loop
+// thread.
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+    let _ = assert_send_sync::<DisplayBuilder>;
+};
+
 fn create_display(
     event_loop: &impl GlutinEventLoop,
     _api_preference: ApiPreference,
     _raw_window_handle: Option<RawWindowHandle>,
+    #[cfg(glx_backend)] glx_error_hook: Option<&GlxErrorHook>,
 ) -> Result<Display, Box<dyn Error>> {
+    #[cfg(glx_backend)]
+    let registrar = || -> XlibErrorHookRegistrar {
+        match glx_error_hook {
+            Some(GlxErrorHook(registrar)) => {
+                let registrar = registrar.clone();
+                Box::new(move |hook| registrar(hook))
+            },
+            None => Box::new(register_xlib_error_hook),
+        }
+    };
+
     #[cfg(egl_backend)]
     let _preference = DisplayApiPreference::Egl;
 
     #[cfg(glx_backend)]
-    let _preference = DisplayApiPreference::Glx(Box::new(register_xlib_error_hook));
+    let _preference = DisplayApiPreference::Glx(registrar());
 
     #[cfg(cgl_backend)]
     let _preference = DisplayApiPreference::Cgl;
@@ -167,12 +294,8 @@ fn create_display(
 
     #[cfg(all(egl_backend, glx_backend))]
     let _preference = match _api_preference {
-        ApiPreference::PreferEgl => {
-            DisplayApiPreference::EglThenGlx(Box::new(register_xlib_error_hook))
-        },
-        ApiPreference::FallbackEgl => {
-            DisplayApiPreference::GlxThenEgl(Box::new(register_xlib_error_hook))
-        },
+        ApiPreference::PreferEgl => DisplayApiPreference::EglThenGlx(registrar()),
+        ApiPreference::FallbackEgl => DisplayApiPreference::GlxThenEgl(registrar()),
     };
 
     #[cfg(all(wgl_backend, egl_backend))]
@@ -189,6 +312,17 @@ fn create_display(
 /// aware that it could remove incompatible options from the window builder like
 /// `transparency`, when the provided config doesn't support it.
 ///
+/// # Platform-specific
+///
+/// - **X11:** the window is created with the visual of the config, which is
+///   required for the surface creation and transparency to work.
+/// - **macOS:** nothing besides the `transparency` is changed. The view is
+///   made non-opaque by the context created from the config picked with
+///   [`ConfigTemplateBuilder::with_transparency`], so when the window is black
+///   instead of transparent, ensure that such config was picked and that the
+///   alpha is cleared to a value lower than `1.0`.
+///
+/// [`ConfigTemplateBuilder::with_transparency`]: glutin::config::ConfigTemplateBuilder::with_transparency
 /// [`Window`]: winit::window::Window
 /// [`Config`]: glutin::config::Config
 pub fn finalize_window(
@@ -211,6 +345,57 @@ pub fn finalize_window(
     event_loop.create_window(attributes)
 }
 
+/// Detect the windowing system backend used by the event loop.
+///
+/// On Linux it's picked at runtime, so this could be used for logging or to
+/// apply platform specific workarounds.
+pub fn backend_in_use(event_loop: &impl GlutinEventLoop) -> Result<Backend, HandleError> {
+    Ok(Backend::from_raw_display_handle(event_loop.glutin_display_handle()?.as_raw()))
+}
+
+/// The windowing system backend used by [`winit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// The X11 backend.
+    X11,
+
+    /// The Wayland backend.
+    Wayland,
+
+    /// The Windows backend.
+    Windows,
+
+    /// The macOS backend.
+    MacOs,
+
+    /// The iOS backend.
+    Ios,
+
+    /// The Android backend.
+    Android,
+
+    /// The Web backend.
+    Web,
+
+    /// The backend not known to glutin.
+    Other,
+}
+
+impl Backend {
+    pub(crate) fn from_raw_display_handle(handle: RawDisplayHandle) -> Self {
+        match handle {
+            RawDisplayHandle::Xlib(_) | RawDisplayHandle::Xcb(_) => Self::X11,
+            RawDisplayHandle::Wayland(_) => Self::Wayland,
+            RawDisplayHandle::Windows(_) => Self::Windows,
+            RawDisplayHandle::AppKit(_) => Self::MacOs,
+            RawDisplayHandle::UiKit(_) => Self::Ios,
+            RawDisplayHandle::Android(_) => Self::Android,
+            RawDisplayHandle::Web(_) => Self::Web,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// Simplified version of the [`DisplayApiPreference`] which is used to simplify
 /// cross platform window creation.
 ///
@@ -1,12 +1,25 @@
+use std::error::Error;
+use std::fmt;
 use std::num::NonZeroU32;
 
-use glutin::context::PossiblyCurrentContext;
+use glutin::config::{Config, ConfigTemplate, ConfigTemplateBuilder};
+use glutin::context::{
+    ContextApi, ContextAttributesBuilder, NotCurrentContext, NotCurrentGlContext,
+    PossiblyCurrentContext,
+};
+use glutin::display::GetGlDisplay;
+use glutin::error::ErrorKind;
+use glutin::prelude::*;
 use glutin::surface::{
-    GlSurface, ResizeableSurface, Surface, SurfaceAttributes, SurfaceAttributesBuilder,
-    SurfaceTypeTrait, WindowSurface,
+    ResizeableSurface, Surface, SurfaceAttributes, SurfaceAttributesBuilder, SurfaceTypeTrait,
+    SwapInterval, WindowSurface,
 };
-use raw_window_handle::{HandleError, HasWindowHandle};
-use winit::window::Window;
+use glutin::BackendFlags;
+use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle};
+use winit::dpi::PhysicalSize;
+use winit::window::{Fullscreen, Window, WindowAttributes};
+
+use crate::{ApiPreference, Backend, GlutinEventLoop};
 
 /// [`Window`] extensions for working with [`glutin`] surfaces.
 pub trait GlWindow {
@@ -45,6 +58,112 @@ pub trait GlWindow {
         surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
         context: &PossiblyCurrentContext,
     );
+
+    /// Request the new window inner size and resize the surface to match it.
+    ///
+    /// When the new size is applied right away, the surface is resized
+    /// immediately and `true` is returned. Otherwise the window is resized
+    /// asynchronously and [`GlWindow::resize_surface`] should be called on the
+    /// next [`WindowEvent::Resized`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::num::NonZeroU32;
+    ///
+    /// use glutin_winit::GlWindow;
+    /// # use glutin::surface::{Surface, WindowSurface};
+    /// # let winit_window: winit::window::Window = unimplemented!();
+    /// # let (gl_surface, gl_context): (Surface<WindowSurface>, _) = unimplemented!();
+    ///
+    /// let size = NonZeroU32::new(512).unwrap();
+    /// winit_window.set_size(&gl_surface, &gl_context, size, size);
+    /// ```
+    ///
+    /// [`WindowEvent::Resized`]: winit::event::WindowEvent::Resized
+    fn set_size(
+        &self,
+        surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
+        context: &PossiblyCurrentContext,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> bool;
+
+    /// Set the window fullscreen state with [`Window::set_fullscreen`] and
+    /// resize the surface to match the new window inner size.
+    ///
+    /// Entering or leaving the fullscreen is asynchronous on most platforms,
+    /// so the surface is only resized right away when the window inner size is
+    /// updated immediately, in which case `true` is returned. Otherwise
+    /// [`GlWindow::resize_or_recreate_surface`] should be called on the next
+    /// [`WindowEvent::Resized`], which also handles the drivers invalidating
+    /// the surface when switching to the exclusive fullscreen.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Wayland:** the new size is only known once the compositor
+    ///   configures the window, thus this always returns `false` and the
+    ///   surface must be reconfigured on [`WindowEvent::Resized`]. The frames
+    ///   drawn before that are presented with the old size.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use glutin_winit::GlWindow;
+    /// use winit::window::Fullscreen;
+    /// # use glutin::surface::{Surface, WindowSurface};
+    /// # let winit_window: winit::window::Window = unimplemented!();
+    /// # let (gl_surface, gl_context): (Surface<WindowSurface>, _) = unimplemented!();
+    ///
+    /// winit_window.set_fullscreen_and_resize(
+    ///     &gl_surface,
+    ///     &gl_context,
+    ///     Some(Fullscreen::Borderless(None)),
+    /// );
+    /// ```
+    ///
+    /// [`WindowEvent::Resized`]: winit::event::WindowEvent::Resized
+    fn set_fullscreen_and_resize(
+        &self,
+        surface: &Surface<WindowSurface>,
+        context: &PossiblyCurrentContext,
+        fullscreen: Option<Fullscreen>,
+    ) -> bool;
+
+    /// Resize the surface to the window inner size, or recreate it when it
+    /// was invalidated.
+    ///
+    /// Some drivers invalidate the surface when switching to the exclusive
+    /// fullscreen, in which case the surface is recreated with
+    /// [`Surface::recreate`] from the `builder` and the window inner size, and
+    /// the `context` is made current with it again. Pass the same `builder`
+    /// the surface was created with to keep its attributes.
+    ///
+    /// This should be called on [`WindowEvent::Resized`] after changing the
+    /// fullscreen state. No-op if either window size is zero.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use glutin_winit::GlWindow;
+    /// # use glutin::surface::{Surface, WindowSurface};
+    /// # let winit_window: winit::window::Window = unimplemented!();
+    /// # let (mut gl_surface, gl_context): (Surface<WindowSurface>, _) = unimplemented!();
+    ///
+    /// winit_window
+    ///     .resize_or_recreate_surface(&mut gl_surface, &gl_context, <_>::default())
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`WindowEvent::Resized`]: winit::event::WindowEvent::Resized
+    fn resize_or_recreate_surface(
+        &self,
+        surface: &mut Surface<WindowSurface>,
+        context: &PossiblyCurrentContext,
+        builder: SurfaceAttributesBuilder<WindowSurface>,
+    ) -> glutin::error::Result<()>;
+
+    /// Detect the windowing system backend the window belongs to.
+    ///
+    /// See [`crate::backend_in_use`] for details.
+    fn backend_in_use(&self) -> Result<Backend, HandleError>;
 }
 
 impl GlWindow for Window {
@@ -52,7 +171,7 @@ impl GlWindow for Window {
         &self,
         builder: SurfaceAttributesBuilder<WindowSurface>,
     ) -> Result<SurfaceAttributes<WindowSurface>, HandleError> {
-        let (w, h) = self.inner_size().non_zero().expect("invalid zero inner size");
+        let (w, h) = physical_size_to_nonzero(self.inner_size()).expect("invalid zero inner size");
         let handle = self.window_handle()?.as_raw();
         Ok(builder.build(handle, w, h))
     }
@@ -62,21 +181,501 @@ impl GlWindow for Window {
         surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
         context: &PossiblyCurrentContext,
     ) {
-        if let Some((w, h)) = self.inner_size().non_zero() {
+        if let Some((w, h)) = physical_size_to_nonzero(self.inner_size()) {
             surface.resize(context, w, h)
         }
     }
+
+    fn set_size(
+        &self,
+        surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
+        context: &PossiblyCurrentContext,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> bool {
+        let size = PhysicalSize::new(width.get(), height.get());
+        match self.request_inner_size(size).and_then(physical_size_to_nonzero) {
+            Some((w, h)) => {
+                surface.resize(context, w, h);
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn set_fullscreen_and_resize(
+        &self,
+        surface: &Surface<WindowSurface>,
+        context: &PossiblyCurrentContext,
+        fullscreen: Option<Fullscreen>,
+    ) -> bool {
+        let old_size = self.inner_size();
+        self.set_fullscreen(fullscreen);
+        let new_size = self.inner_size();
+        if new_size == old_size {
+            return false;
+        }
+
+        match physical_size_to_nonzero(new_size) {
+            Some((w, h)) => {
+                surface.resize(context, w, h);
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn resize_or_recreate_surface(
+        &self,
+        surface: &mut Surface<WindowSurface>,
+        context: &PossiblyCurrentContext,
+        builder: SurfaceAttributesBuilder<WindowSurface>,
+    ) -> glutin::error::Result<()> {
+        let (w, h) = match physical_size_to_nonzero(self.inner_size()) {
+            Some(size) => size,
+            None => return Ok(()),
+        };
+
+        if surface.is_valid() {
+            surface.resize(context, w, h);
+            return Ok(());
+        }
+
+        let handle = self.window_handle().map_err(|_| ErrorKind::BadNativeWindow)?.as_raw();
+        let attributes = builder.build(handle, w, h);
+        // SAFETY: the handle belongs to the window, which is alive.
+        unsafe { surface.recreate(&attributes)? };
+        context.make_current(surface)
+    }
+
+    fn backend_in_use(&self) -> Result<Backend, HandleError> {
+        Ok(Backend::from_raw_display_handle(self.display_handle()?.as_raw()))
+    }
+}
+
+/// The [`Window`] along with the [`Surface`] rendering into it.
+///
+/// This is a batteries-included way to get a window with a GL context, for
+/// the control over the config selection use [`DisplayBuilder`].
+///
+/// The surface is always destroyed before the window it was created from,
+/// since it references the window's native handle.
+///
+/// [`DisplayBuilder`]: crate::DisplayBuilder
+#[derive(Debug)]
+pub struct SimpleGlWindow {
+    // NOTE: the fields are dropped in the declaration order, so the surface must
+    // be declared before the window it was created from.
+    surface: Surface<WindowSurface>,
+    window: Window,
+}
+
+impl SimpleGlWindow {
+    /// Create the window with the context current on it.
+    ///
+    /// EGL is preferred, the config with the alpha and the most samples is
+    /// picked, and the OpenGL context is tried before the OpenGL ES one.
+    /// Vsync is enabled when supported.
+    ///
+    /// The [`ErrorKind::NoMatchingConfig`] is returned when the display has no
+    /// configs matching the template. When no context could be created, the
+    /// errors of the previous attempts are available through [`source`].
+    ///
+    /// [`ErrorKind::NoMatchingConfig`]: glutin::error::ErrorKind::NoMatchingConfig
+    /// [`source`]: std::error::Error::source
+    pub fn new(
+        event_loop: &impl GlutinEventLoop,
+        window_attributes: WindowAttributes,
+    ) -> Result<(Self, PossiblyCurrentContext), Box<dyn Error>> {
+        let mut report = BootstrapReport::new(event_loop);
+        Self::bootstrap(event_loop, window_attributes, &mut report).map_err(|(_, err)| err)
+    }
+
+    /// The window.
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
+    /// The surface rendering into the window.
+    pub fn surface(&self) -> &Surface<WindowSurface> {
+        &self.surface
+    }
+}
+
+impl SimpleGlWindow {
+    /// The same as [`SimpleGlWindow::new`], but on failure the
+    /// [`BootstrapReport`] describing the bootstrap flow is returned, which
+    /// could be printed and attached to the bug report.
+    pub fn new_diagnostic(
+        event_loop: &impl GlutinEventLoop,
+        window_attributes: WindowAttributes,
+    ) -> Result<(Self, PossiblyCurrentContext), Box<BootstrapReport>> {
+        let mut report = BootstrapReport::new(event_loop);
+        match Self::bootstrap(event_loop, window_attributes, &mut report) {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                report.errors.push(error);
+                Err(Box::new(report))
+            },
+        }
+    }
+
+    /// Create the display, the window, the context, and the surface, recording
+    /// the progress in the `report`.
+    fn bootstrap(
+        event_loop: &impl GlutinEventLoop,
+        window_attributes: WindowAttributes,
+        report: &mut BootstrapReport,
+    ) -> Result<(Self, PossiblyCurrentContext), (BootstrapStep, Box<dyn Error>)> {
+        let template = bootstrap_template();
+
+        // XXX with WGL backend window should be created first.
+        #[cfg(wgl_backend)]
+        let window = event_loop
+            .create_window(window_attributes)
+            .map_err(BootstrapStep::CreateWindow.failed())?;
+        #[cfg(wgl_backend)]
+        let raw_window_handle =
+            Some(window.window_handle().map_err(BootstrapStep::CreateWindow.failed())?.as_raw());
+        #[cfg(not(wgl_backend))]
+        let raw_window_handle = None;
+
+        let display = crate::create_display(
+            event_loop,
+            report.api_preference,
+            raw_window_handle,
+            #[cfg(glx_backend)]
+            None,
+        )
+        .map_err(BootstrapStep::CreateDisplay.failed())?;
+        report.display = Some(display.version_string());
+
+        #[cfg(wgl_backend)]
+        let template = template.compatible_with_native_window(raw_window_handle.unwrap());
+        let template = template.build();
+        report.template = template.clone();
+
+        let configs = unsafe { display.find_configs_or_err(template) }.map_err(|err| {
+            if err.error_kind() == ErrorKind::NoMatchingConfig {
+                report.num_configs = Some(0);
+            }
+            BootstrapStep::FindConfigs.failed()(err)
+        })?;
+        report.num_configs = Some(configs.len());
+        // The configs are never empty.
+        let config = configs.into_iter().max_by_key(|config| config.num_samples()).unwrap();
+        report.config = Some(config.to_string());
+
+        #[cfg(not(wgl_backend))]
+        let window = crate::finalize_window(event_loop, window_attributes, &config)
+            .map_err(BootstrapStep::CreateWindow.failed())?;
+
+        let raw_window_handle =
+            Some(window.window_handle().map_err(BootstrapStep::CreateWindow.failed())?.as_raw());
+        let context_attributes = [
+            ContextAttributesBuilder::new().build(raw_window_handle),
+            ContextAttributesBuilder::new()
+                .with_context_api(ContextApi::Gles(None))
+                .build(raw_window_handle),
+        ];
+
+        let (context, _) =
+            unsafe { display.create_context_first_supported(&config, &context_attributes) }
+                .map_err(|err| {
+                    // The error of the last attempt chains the previous ones, record all of
+                    // them in the order they happened.
+                    let mut previous = Vec::new();
+                    let mut source = err.source();
+                    while let Some(error) =
+                        source.and_then(|source| source.downcast_ref::<glutin::error::Error>())
+                    {
+                        previous.push((BootstrapStep::CreateContext, error.clone().into()));
+                        source = error.source();
+                    }
+                    report.errors.extend(previous.into_iter().rev());
+                    BootstrapStep::CreateContext.failed()(err)
+                })?;
+
+        let surface_attributes = window
+            .build_surface_attributes(Default::default())
+            .map_err(BootstrapStep::CreateSurface.failed())?;
+        let surface = unsafe { display.create_window_surface(&config, &surface_attributes) }
+            .map_err(BootstrapStep::CreateSurface.failed())?;
+        let context =
+            context.make_current(&surface).map_err(BootstrapStep::MakeCurrent.failed())?;
+
+        // Vsync is not crucial, so ignore the error.
+        let _ = surface.set_swap_interval(&context, SwapInterval::Wait(NonZeroU32::MIN));
+
+        Ok((Self { surface, window }, context))
+    }
+}
+
+/// The step of the bootstrap flow performed by
+/// [`SimpleGlWindow::new_diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BootstrapStep {
+    /// The [`Display`] creation.
+    ///
+    /// [`Display`]: glutin::display::Display
+    CreateDisplay,
+
+    /// Finding the configs matching the template.
+    FindConfigs,
+
+    /// The [`Window`] creation.
+    CreateWindow,
+
+    /// The context creation.
+    CreateContext,
+
+    /// The window surface creation.
+    CreateSurface,
+
+    /// Making the context current with the surface.
+    MakeCurrent,
+}
+
+impl BootstrapStep {
+    fn failed<E: Into<Box<dyn Error>>>(self) -> impl FnOnce(E) -> (Self, Box<dyn Error>) {
+        move |err| (self, err.into())
+    }
+}
+
+impl fmt::Display for BootstrapStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let step = match self {
+            Self::CreateDisplay => "display creation",
+            Self::FindConfigs => "config search",
+            Self::CreateWindow => "window creation",
+            Self::CreateContext => "context creation",
+            Self::CreateSurface => "surface creation",
+            Self::MakeCurrent => "make current",
+        };
+        f.write_str(step)
+    }
+}
+
+/// The report of the failed [`SimpleGlWindow::new_diagnostic`].
+///
+/// The [`Display`] implementation formats it as the block suitable for the bug
+/// reports.
+///
+/// [`Display`]: std::fmt::Display
+#[derive(Debug)]
+pub struct BootstrapReport {
+    /// The windowing system backend used by the event loop, when it could be
+    /// detected.
+    pub windowing_backend: Option<Backend>,
+
+    /// The graphics platform interfaces glutin was built with, which were
+    /// tried according to the [`Self::api_preference`].
+    pub backends: BackendFlags,
+
+    /// The preference used to create the display.
+    pub api_preference: ApiPreference,
+
+    /// The template used to find the configs.
+    pub template: ConfigTemplate,
+
+    /// The [`GlDisplay::version_string`] of the created display.
+    pub display: Option<String>,
+
+    /// The number of configs matching the [`Self::template`].
+    pub num_configs: Option<usize>,
+
+    /// The description of the picked config.
+    pub config: Option<String>,
+
+    /// The errors in the order they happened, the last one stopped the
+    /// bootstrap.
+    pub errors: Vec<(BootstrapStep, Box<dyn Error>)>,
+}
+
+impl fmt::Display for BootstrapReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "glutin bootstrap failed")?;
+        writeln!(f, "windowing backend: {:?}", self.windowing_backend)?;
+        writeln!(f, "compiled backends: {:?}", self.backends)?;
+        writeln!(f, "api preference: {:?}", self.api_preference)?;
+        writeln!(f, "display: {}", self.display.as_deref().unwrap_or("none"))?;
+        writeln!(f, "template: {:?}", self.template)?;
+        match self.num_configs {
+            Some(num_configs) => writeln!(f, "configs found: {num_configs}")?,
+            None => writeln!(f, "configs found: none")?,
+        }
+        writeln!(f, "config: {}", self.config.as_deref().unwrap_or("none"))?;
+        write!(f, "errors:")?;
+        for (step, error) in &self.errors {
+            write!(f, "\n  {step}: {error}")?;
+        }
+        Ok(())
+    }
 }
 
-/// [`winit::dpi::PhysicalSize<u32>`] non-zero extensions.
-trait NonZeroU32PhysicalSize {
-    /// Converts to non-zero `(width, height)`.
-    fn non_zero(self) -> Option<(NonZeroU32, NonZeroU32)>;
+impl Error for BootstrapReport {}
+
+impl BootstrapReport {
+    /// The report of the bootstrap not started yet.
+    fn new(event_loop: &impl GlutinEventLoop) -> Self {
+        Self {
+            windowing_backend: crate::backend_in_use(event_loop).ok(),
+            backends: glutin::COMPILED_BACKENDS,
+            api_preference: ApiPreference::PreferEgl,
+            template: bootstrap_template().build(),
+            display: None,
+            num_configs: None,
+            config: None,
+            errors: Vec::new(),
+        }
+    }
 }
-impl NonZeroU32PhysicalSize for winit::dpi::PhysicalSize<u32> {
-    fn non_zero(self) -> Option<(NonZeroU32, NonZeroU32)> {
-        let w = NonZeroU32::new(self.width)?;
-        let h = NonZeroU32::new(self.height)?;
-        Some((w, h))
+
+/// The template used by [`SimpleGlWindow`] to find the configs.
+fn bootstrap_template() -> ConfigTemplateBuilder {
+    ConfigTemplateBuilder::new().with_alpha_size(8)
+}
+
+/// The context kept across the suspend and resume cycle, with the [`Window`]
+/// and the [`Surface`] recreated on every resume.
+///
+/// This is meant for the platforms like Android, where the native window is
+/// only available between [`ApplicationHandler::resumed`] and
+/// [`ApplicationHandler::suspended`], so the window along with the surface
+/// must be destroyed on suspend, while the context could be reused.
+///
+/// ```no_run
+/// # use glutin::config::Config;
+/// # use glutin::context::NotCurrentContext;
+/// # use winit::event_loop::ActiveEventLoop;
+/// # use winit::window::Window;
+/// use glutin_winit::ResumableGlWindow;
+///
+/// # fn lifecycle(event_loop: &ActiveEventLoop, config: Config, context: NotCurrentContext) {
+/// let mut gl_window = ResumableGlWindow::new(config, context);
+///
+/// // In `ApplicationHandler::resumed`.
+/// gl_window.resume(event_loop, Window::default_attributes()).unwrap();
+///
+/// // In `ApplicationHandler::suspended`.
+/// gl_window.suspend().unwrap();
+/// # }
+/// ```
+///
+/// [`ApplicationHandler::resumed`]: winit::application::ApplicationHandler::resumed
+/// [`ApplicationHandler::suspended`]: winit::application::ApplicationHandler::suspended
+#[derive(Debug)]
+pub struct ResumableGlWindow {
+    config: Config,
+    context: PossiblyCurrentContext,
+    // NOTE: the surface must be dropped before the window it was created from.
+    surface: Option<Surface<WindowSurface>>,
+    window: Option<Window>,
+}
+
+impl ResumableGlWindow {
+    /// Create the suspended window state from the picked config and the
+    /// context created with it.
+    pub fn new(config: Config, context: NotCurrentContext) -> Self {
+        Self { config, context: context.treat_as_possibly_current(), surface: None, window: None }
+    }
+
+    /// Create the window from the `window_attributes` with
+    /// [`finalize_window`], then create the surface for it and make the
+    /// context current.
+    ///
+    /// The window and the surface from the previous resume, if any, are
+    /// destroyed.
+    ///
+    /// [`finalize_window`]: crate::finalize_window
+    pub fn resume(
+        &mut self,
+        event_loop: &impl GlutinEventLoop,
+        window_attributes: WindowAttributes,
+    ) -> Result<(), Box<dyn Error>> {
+        self.suspend()?;
+        let window = crate::finalize_window(event_loop, window_attributes, &self.config)?;
+        self.resume_with_window(window)
     }
+
+    /// The same as [`Self::resume`], but with the window already created,
+    /// e.g. the one from [`DisplayBuilder::build`] on the first resume.
+    ///
+    /// [`DisplayBuilder::build`]: crate::DisplayBuilder::build
+    pub fn resume_with_window(&mut self, window: Window) -> Result<(), Box<dyn Error>> {
+        self.suspend()?;
+
+        let surface_attributes = window.build_surface_attributes(Default::default())?;
+        let surface = unsafe {
+            self.config.display().create_window_surface(&self.config, &surface_attributes)?
+        };
+        self.context.make_current(&surface)?;
+
+        self.surface = Some(surface);
+        self.window = Some(window);
+        Ok(())
+    }
+
+    /// Make the context not current and destroy the surface along with the
+    /// window.
+    ///
+    /// Does nothing when already suspended.
+    pub fn suspend(&mut self) -> Result<(), glutin::error::Error> {
+        if self.window.is_none() {
+            return Ok(());
+        }
+
+        self.context.make_not_current_in_place()?;
+        self.surface = None;
+        self.window = None;
+        Ok(())
+    }
+
+    /// Whether the window and the surface are not present.
+    pub fn is_suspended(&self) -> bool {
+        self.window.is_none()
+    }
+
+    /// The window, present only while resumed.
+    pub fn window(&self) -> Option<&Window> {
+        self.window.as_ref()
+    }
+
+    /// The surface rendering into the window, present only while resumed.
+    pub fn surface(&self) -> Option<&Surface<WindowSurface>> {
+        self.surface.as_ref()
+    }
+
+    /// The context, which is current on the surface while resumed.
+    pub fn context(&self) -> &PossiblyCurrentContext {
+        &self.context
+    }
+
+    /// The config used for the context and the surfaces.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+}
+
+/// Convert the [`PhysicalSize`] into the non-zero `(width, height)` pair,
+/// as used by [`GlSurface::resize`].
+///
+/// Returns `None` when either of the dimensions is zero, e.g. when the window
+/// is minimized.
+///
+/// ```no_run
+/// # use glutin::context::PossiblyCurrentContext;
+/// # use glutin::prelude::*;
+/// # use glutin::surface::{Surface, WindowSurface};
+/// # use winit::dpi::PhysicalSize;
+/// # fn resize(surface: &Surface<WindowSurface>, context: &PossiblyCurrentContext, size: PhysicalSize<u32>) {
+/// if let Some((width, height)) = glutin_winit::physical_size_to_nonzero(size) {
+///     surface.resize(context, width, height);
+/// }
+/// # }
+/// ```
+pub fn physical_size_to_nonzero(size: PhysicalSize<u32>) -> Option<(NonZeroU32, NonZeroU32)> {
+    let w = NonZeroU32::new(size.width)?;
+    let h = NonZeroU32::new(size.height)?;
+    Some((w, h))
 }
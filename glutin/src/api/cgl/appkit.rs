@@ -75,6 +75,12 @@ extern_methods!(
 
         #[method(CGLContextObj)]
         pub(crate) fn CGLContextObj(&self) -> *mut CGLContextObj;
+
+        #[method(currentVirtualScreen)]
+        pub(crate) fn currentVirtualScreen(&self) -> GLint;
+
+        #[method(setCurrentVirtualScreen:)]
+        pub(crate) fn setCurrentVirtualScreen(&self, screen: GLint);
     }
 );
 
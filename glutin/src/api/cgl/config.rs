@@ -1,5 +1,6 @@
 //! Everything related to `NSOpenGLPixelFormat`.
 
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 use std::{fmt, iter};
 
@@ -16,6 +17,7 @@ use objc2_app_kit::{
 
 use crate::config::{
     Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, RawConfig,
+    Transparency,
 };
 use crate::display::GetGlDisplay;
 use crate::error::{ErrorKind, Result};
@@ -115,7 +117,7 @@ impl Display {
             // initWithAttributes returns None if the attributes were invalid
             unsafe { NSOpenGLPixelFormat::newWithAttributes(&attrs) }
         })
-        .ok_or(ErrorKind::BadConfig)?;
+        .ok_or(ErrorKind::NoMatchingConfig)?;
 
         let inner = Arc::new(ConfigInner {
             display: self.clone(),
@@ -204,9 +206,33 @@ impl GlConfig for Config {
         Some(self.inner.transparency)
     }
 
+    fn transparency(&self) -> Option<Transparency> {
+        if self.inner.transparency {
+            Some(Transparency::AlphaBlend)
+        } else {
+            Some(Transparency::Opaque)
+        }
+    }
+
     fn api(&self) -> Api {
         Api::OPENGL
     }
+
+    fn conformant_apis(&self) -> Option<Api> {
+        None
+    }
+
+    fn max_pbuffer_size(&self) -> Option<(u32, u32, u32)> {
+        None
+    }
+
+    fn native_renderable(&self) -> Option<bool> {
+        None
+    }
+
+    fn swap_interval_range(&self) -> Option<RangeInclusive<u32>> {
+        None
+    }
 }
 
 impl GetGlDisplay for Config {
@@ -3,7 +3,7 @@
 use std::fmt;
 use std::marker::PhantomData;
 
-use cgl::CGLSetParameter;
+use cgl::{CGLEnable, CGLSetParameter};
 use objc2::rc::{autoreleasepool, Id};
 use objc2::ClassType;
 use objc2_app_kit::{NSOpenGLCPSwapInterval, NSView};
@@ -11,7 +11,8 @@ use objc2_foundation::{run_on_main, MainThreadBound};
 
 use crate::config::GetGlConfig;
 use crate::context::{
-    AsRawContext, ContextApi, ContextAttributes, Priority, RawContext, Robustness,
+    AsRawContext, ContextApi, ContextAttributes, CreationAttributes, DebugCallbackSlot, Priority,
+    RawContext, ReleaseBehavior, Robustness,
 };
 use crate::display::GetGlDisplay;
 use crate::error::{ErrorKind, Result};
@@ -19,7 +20,7 @@ use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::{SurfaceTypeTrait, SwapInterval};
 
-use super::appkit::NSOpenGLContext;
+use super::appkit::{GLint, NSOpenGLContext};
 use super::config::Config;
 use super::display::Display;
 use super::surface::Surface;
@@ -30,11 +31,27 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        context_attributes.with_robustness_fallback(|context_attributes| unsafe {
+            self.create_context_with_attributes(config, context_attributes)
+        })
+    }
+
+    unsafe fn create_context_with_attributes(
+        &self,
+        config: &Config,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext> {
+        if context_attributes.protected {
+            return Err(ErrorKind::NotSupported("protected content is not supported").into());
+        }
+
         let share_context = match context_attributes.shared_context.as_ref() {
             Some(RawContext::Cgl(share_context)) => unsafe {
                 share_context.cast::<NSOpenGLContext>().as_ref()
             },
-            _ => None,
+            None => None,
+            #[allow(unreachable_patterns)]
+            _ => return Err(ErrorKind::NotSupported("incompatible context was passed").into()),
         };
 
         if matches!(context_attributes.api, Some(ContextApi::Gles(_))) {
@@ -45,6 +62,18 @@ impl Display {
             return Err(ErrorKind::NotSupported("robustness is not supported with CGL").into());
         }
 
+        if context_attributes.priority.is_some() {
+            context_attributes.ignore_attribute("context priority is not supported")?;
+        }
+
+        if context_attributes.debug {
+            context_attributes.ignore_attribute("debug context is not supported")?;
+        }
+
+        if context_attributes.release_behavior != ReleaseBehavior::Flush {
+            context_attributes.ignore_attribute("context release behavior is not supported")?;
+        }
+
         let config = config.clone();
         let raw = NSOpenGLContext::initWithFormat_shareContext(
             NSOpenGLContext::alloc(),
@@ -60,7 +89,24 @@ impl Display {
             })?;
         }
 
-        let inner = ContextInner { display: self.clone(), config, raw };
+        if let Some(virtual_screen) = context_attributes.virtual_screen {
+            raw.setCurrentVirtualScreen(virtual_screen as GLint);
+        }
+
+        if context_attributes.crash_on_removed_functions {
+            super::check_error(unsafe {
+                CGLEnable(raw.CGLContextObj().cast(), cgl::kCGLCECrashOnRemovedFunctions)
+            })?;
+        }
+
+        let inner = ContextInner {
+            display: self.clone(),
+            config,
+            raw,
+            robustness: context_attributes.robustness,
+            attributes: CreationAttributes::new(context_attributes),
+            debug_callback: Default::default(),
+        };
         let context = NotCurrentContext::new(inner);
 
         Ok(context)
@@ -119,6 +165,10 @@ impl GlContext for NotCurrentContext {
     fn priority(&self) -> Priority {
         Priority::Medium
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl GetGlConfig for NotCurrentContext {
@@ -200,6 +250,10 @@ impl GlContext for PossiblyCurrentContext {
     fn priority(&self) -> Priority {
         Priority::Medium
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl GetGlConfig for PossiblyCurrentContext {
@@ -226,10 +280,57 @@ impl AsRawContext for PossiblyCurrentContext {
 
 impl Sealed for PossiblyCurrentContext {}
 
+impl PossiblyCurrentContext {
+    pub(crate) fn context_attributes(&self) -> &ContextAttributes {
+        &self.inner.attributes
+    }
+
+    pub(crate) fn debug_callback(&self) -> &DebugCallbackSlot {
+        &self.inner.debug_callback
+    }
+
+    /// The virtual screen, selecting the renderer, the context is using.
+    pub fn virtual_screen(&self) -> u32 {
+        self.inner.raw.currentVirtualScreen() as u32
+    }
+
+    /// Move the context to the given virtual screen, e.g. to render with the
+    /// GPU driving the display the window is on.
+    pub fn set_virtual_screen(&self, virtual_screen: u32) {
+        self.inner.raw.setCurrentVirtualScreen(virtual_screen as GLint);
+    }
+
+    /// Save the context current on the thread.
+    pub(crate) fn save_current(&self) -> CurrentState {
+        CurrentState { context: NSOpenGLContext::currentContext() }
+    }
+
+    /// Restore the state saved with [`Self::save_current`].
+    pub(crate) fn restore_current(&self, state: &CurrentState) -> Result<()> {
+        autoreleasepool(|_| match state.context.as_ref() {
+            Some(context) => context.makeCurrentContext(),
+            None => NSOpenGLContext::clearCurrentContext(),
+        });
+
+        Ok(())
+    }
+}
+
+/// The current context of the thread saved by the [`CurrentContextGuard`].
+///
+/// [`CurrentContextGuard`]: crate::context::CurrentContextGuard
+#[derive(Debug)]
+pub(crate) struct CurrentState {
+    context: Option<Id<NSOpenGLContext>>,
+}
+
 pub(crate) struct ContextInner {
     display: Display,
     config: Config,
     pub(crate) raw: Id<NSOpenGLContext>,
+    robustness: Robustness,
+    attributes: CreationAttributes,
+    debug_callback: DebugCallbackSlot,
 }
 
 impl ContextInner {
@@ -60,5 +60,5 @@ pub(crate) fn check_error(error: CGLError) -> Result<()> {
 
     let description =
         unsafe { CStr::from_ptr(CGLErrorString(error)).to_str().unwrap_or_default().to_string() };
-    Err(Error::new(Some(error as _), Some(description), kind))
+    Err(Error::new(Some(error as _), Some(description), kind).record_native("CGL"))
 }
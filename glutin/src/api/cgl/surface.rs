@@ -3,6 +3,7 @@
 use std::fmt;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
+use std::sync::atomic::AtomicBool;
 
 use objc2::rc::Id;
 use objc2_app_kit::{NSAppKitVersionNumber, NSAppKitVersionNumber10_12, NSView};
@@ -44,6 +45,8 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<WindowSurface>,
     ) -> Result<Surface<WindowSurface>> {
+        surface_attributes.check_protected_unsupported()?;
+
         let native_window = match surface_attributes.raw_window_handle.unwrap() {
             RawWindowHandle::AppKit(window) => window,
             _ => {
@@ -90,6 +93,7 @@ impl Display {
             config: config.clone(),
             ns_view,
             _nosync: PhantomData,
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         };
         Ok(surface)
@@ -102,6 +106,7 @@ pub struct Surface<T: SurfaceTypeTrait> {
     config: Config,
     pub(crate) ns_view: MainThreadBound<Id<NSView>>,
     _nosync: PhantomData<*const std::ffi::c_void>,
+    pub(crate) swap_interval_set: AtomicBool,
     _ty: PhantomData<T>,
 }
 
@@ -1,17 +1,18 @@
 //! Everything related to finding and manipulating the `EGLConfig`.
 #![allow(clippy::unnecessary_cast)] // needed for 32bit & 64bit support
 
-use std::ops::Deref;
+use std::ops::{Deref, RangeInclusive};
 use std::sync::Arc;
 use std::{fmt, mem};
 
+use once_cell::sync::OnceCell;
 use raw_window_handle::RawWindowHandle;
 
 use glutin_egl_sys::egl;
 use glutin_egl_sys::egl::types::{EGLConfig, EGLint};
 
 use crate::config::{
-    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, RawConfig,
+    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, RawConfig, Transparency,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
@@ -69,6 +70,12 @@ impl Display {
             return Err(ErrorKind::NotSupported("float pixels not supported").into());
         }
 
+        if template.srgb_capable
+            && !self.inner.features.contains(DisplayFeatures::SRGB_FRAMEBUFFERS)
+        {
+            return Err(ErrorKind::NotSupported("sRGB framebuffers are not supported").into());
+        }
+
         // Add alpha.
         config_attributes.push(egl::ALPHA_SIZE as EGLint);
         config_attributes.push(template.alpha_size as EGLint);
@@ -127,20 +134,7 @@ impl Display {
 
         config_attributes.push(egl::RENDERABLE_TYPE as EGLint);
         let api = if let Some(requested_api) = template.api {
-            let mut api = 0;
-            if requested_api.contains(Api::GLES1) {
-                api |= egl::OPENGL_ES_BIT;
-            }
-            if requested_api.contains(Api::GLES2) {
-                api |= egl::OPENGL_ES2_BIT;
-            }
-            if requested_api.contains(Api::GLES3) {
-                api |= egl::OPENGL_ES3_BIT;
-            }
-            if requested_api.contains(Api::OPENGL) {
-                api |= egl::OPENGL_BIT;
-            }
-            api
+            api_to_egl_bits(requested_api)
         } else {
             // NOTE: use ES2 by default to avoid matching pure ES1 configs,
             // for more see https://github.com/rust-windowing/glutin/issues/1586.
@@ -148,6 +142,18 @@ impl Display {
         };
         config_attributes.push(api as EGLint);
 
+        // Add conformance requirements.
+        if let Some(conformant_api) = template.conformant_api {
+            config_attributes.push(egl::CONFORMANT as EGLint);
+            config_attributes.push(api_to_egl_bits(conformant_api) as EGLint);
+        }
+
+        // Add native rendering requirement.
+        if template.native_renderable {
+            config_attributes.push(egl::NATIVE_RENDERABLE as EGLint);
+            config_attributes.push(egl::TRUE as EGLint);
+        }
+
         // Add maximum height of pbuffer.
         if let Some(pbuffer_width) = template.max_pbuffer_width {
             config_attributes.push(egl::MAX_PBUFFER_WIDTH as EGLint);
@@ -187,7 +193,7 @@ impl Display {
             .into_iter()
             .map(move |raw| {
                 let raw = EglConfig(raw);
-                let inner = Arc::new(ConfigInner { display: self.clone(), raw });
+                let inner = Arc::new(ConfigInner::new(self.clone(), raw));
                 Config { inner }
             })
             .filter(move |config| {
@@ -197,7 +203,7 @@ impl Display {
                 // when calling `eglChooseConfig` since the visual is ignored.
                 match template.native_window {
                     Some(RawWindowHandle::Xcb(xcb)) => {
-                        xcb.visual_id.map_or(false, |id| id.get() == config.native_visual())
+                        xcb.visual_id.is_some_and(|id| id.get() == config.native_visual())
                     },
                     Some(RawWindowHandle::Xlib(xlib)) if xlib.visual_id > 0 => {
                         xlib.visual_id as u32 == config.native_visual()
@@ -207,7 +213,15 @@ impl Display {
             })
             .filter(move |config| {
                 !template.transparency || config.supports_transparency().unwrap_or(true)
-            });
+            })
+            .filter(move |config| {
+                template.min_swap_interval_max.map_or(true, |min_swap_interval_max| {
+                    config
+                        .swap_interval_range()
+                        .is_some_and(|range| *range.end() >= min_swap_interval_max)
+                })
+            })
+            .filter(move |config| template.fits_pbuffer_sizes(|| config.max_pbuffer_size()));
 
         Ok(Box::new(configs))
     }
@@ -229,6 +243,22 @@ pub struct Config {
 }
 
 impl Config {
+    /// The config standing for `EGL_NO_CONFIG_KHR`.
+    pub(crate) fn no_config(display: &Display) -> Self {
+        let raw = EglConfig(egl::NO_CONFIG_KHR);
+        Self { inner: Arc::new(ConfigInner::new(display.clone(), raw)) }
+    }
+
+    /// The `EGL_CONFIG_ID` of the config.
+    pub(crate) fn config_id(&self) -> u32 {
+        unsafe { self.raw_attribute(egl::CONFIG_ID as EGLint) as u32 }
+    }
+
+    /// Whether the config is `EGL_NO_CONFIG_KHR`.
+    pub(crate) fn is_no_config(&self) -> bool {
+        std::ptr::eq(*self.inner.raw, egl::NO_CONFIG_KHR)
+    }
+
     /// The native visual identifier.
     ///
     /// The interpretation of this value is platform dependant. Consult
@@ -237,10 +267,25 @@ impl Config {
         unsafe { self.raw_attribute(egl::NATIVE_VISUAL_ID as EGLint) as u32 }
     }
 
+    /// The attributes from [`CACHED_ATTRIBUTES`] are queried on the first
+    /// access and served from the cache afterwards.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
     unsafe fn raw_attribute(&self, attr: EGLint) -> EGLint {
+        match CACHED_ATTRIBUTES.binary_search(&(attr as _)) {
+            Ok(index) => {
+                *self.inner.attributes[index].get_or_init(|| unsafe { self.query_attribute(attr) })
+            },
+            Err(_) => unsafe { self.query_attribute(attr) },
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that the attribute could be present.
+    unsafe fn query_attribute(&self, attr: EGLint) -> EGLint {
         unsafe {
             let mut val = 0;
             self.inner.display.inner.egl.GetConfigAttrib(
@@ -340,23 +385,56 @@ impl GlConfig for Config {
         }
     }
 
+    fn transparency(&self) -> Option<Transparency> {
+        unsafe {
+            if self.raw_attribute(egl::TRANSPARENT_TYPE as EGLint) == egl::TRANSPARENT_RGB as EGLint
+            {
+                return Some(Transparency::ChromaKey {
+                    r: self.raw_attribute(egl::TRANSPARENT_RED_VALUE as EGLint) as u32,
+                    g: self.raw_attribute(egl::TRANSPARENT_GREEN_VALUE as EGLint) as u32,
+                    b: self.raw_attribute(egl::TRANSPARENT_BLUE_VALUE as EGLint) as u32,
+                });
+            }
+        }
+
+        self.supports_transparency().map(|alpha| {
+            if alpha {
+                Transparency::AlphaBlend
+            } else {
+                Transparency::Opaque
+            }
+        })
+    }
+
     fn api(&self) -> Api {
-        let mut api = Api::empty();
         let raw_api = unsafe { self.raw_attribute(egl::RENDERABLE_TYPE as EGLint) as u32 };
-        if raw_api & egl::OPENGL_BIT as u32 != 0 {
-            api.insert(Api::OPENGL);
-        }
-        if raw_api & egl::OPENGL_ES_BIT as u32 != 0 {
-            api.insert(Api::GLES1);
-        }
-        if raw_api & egl::OPENGL_ES2_BIT as u32 != 0 {
-            api.insert(Api::GLES2);
-        }
-        if raw_api & egl::OPENGL_ES3_BIT as u32 != 0 {
-            api.insert(Api::GLES3);
+        api_from_egl_bits(raw_api)
+    }
+
+    fn conformant_apis(&self) -> Option<Api> {
+        let raw_api = unsafe { self.raw_attribute(egl::CONFORMANT as EGLint) as u32 };
+        Some(api_from_egl_bits(raw_api))
+    }
+
+    fn max_pbuffer_size(&self) -> Option<(u32, u32, u32)> {
+        unsafe {
+            let width = self.raw_attribute(egl::MAX_PBUFFER_WIDTH as EGLint) as u32;
+            let height = self.raw_attribute(egl::MAX_PBUFFER_HEIGHT as EGLint) as u32;
+            let pixels = self.raw_attribute(egl::MAX_PBUFFER_PIXELS as EGLint) as u32;
+            Some((width, height, pixels))
         }
+    }
+
+    fn native_renderable(&self) -> Option<bool> {
+        unsafe { Some(self.raw_attribute(egl::NATIVE_RENDERABLE as EGLint) != 0) }
+    }
 
-        api
+    fn swap_interval_range(&self) -> Option<RangeInclusive<u32>> {
+        unsafe {
+            let min = self.raw_attribute(egl::MIN_SWAP_INTERVAL as EGLint) as u32;
+            let max = self.raw_attribute(egl::MAX_SWAP_INTERVAL as EGLint) as u32;
+            Some(min..=max)
+        }
     }
 }
 
@@ -389,9 +467,57 @@ impl X11GlConfigExt for Config {
 
 impl Sealed for Config {}
 
+/// The attributes backing the [`GlConfig`] accessors, which are cached, since
+/// the config pickers usually inspect them across all the configs.
+///
+/// The attributes are sorted to look them up with the binary search.
+const CACHED_ATTRIBUTES: [egl::types::EGLenum; 25] = [
+    egl::ALPHA_SIZE,
+    egl::BLUE_SIZE,
+    egl::GREEN_SIZE,
+    egl::RED_SIZE,
+    egl::DEPTH_SIZE,
+    egl::STENCIL_SIZE,
+    egl::CONFIG_CAVEAT,
+    egl::CONFIG_ID,
+    egl::MAX_PBUFFER_HEIGHT,
+    egl::MAX_PBUFFER_PIXELS,
+    egl::MAX_PBUFFER_WIDTH,
+    egl::NATIVE_RENDERABLE,
+    egl::NATIVE_VISUAL_ID,
+    egl::SAMPLES,
+    egl::SURFACE_TYPE,
+    egl::TRANSPARENT_TYPE,
+    egl::TRANSPARENT_BLUE_VALUE,
+    egl::TRANSPARENT_GREEN_VALUE,
+    egl::TRANSPARENT_RED_VALUE,
+    egl::MIN_SWAP_INTERVAL,
+    egl::MAX_SWAP_INTERVAL,
+    egl::LUMINANCE_SIZE,
+    egl::COLOR_BUFFER_TYPE,
+    egl::RENDERABLE_TYPE,
+    egl::CONFORMANT,
+];
+
+// Fail the build when the attributes aren't sorted.
+const _: () = {
+    let mut index = 1;
+    while index < CACHED_ATTRIBUTES.len() {
+        assert!(CACHED_ATTRIBUTES[index - 1] < CACHED_ATTRIBUTES[index]);
+        index += 1;
+    }
+};
+
 pub(crate) struct ConfigInner {
     display: Display,
     pub(crate) raw: EglConfig,
+    attributes: [OnceCell<EGLint>; CACHED_ATTRIBUTES.len()],
+}
+
+impl ConfigInner {
+    fn new(display: Display, raw: EglConfig) -> Self {
+        Self { display, raw, attributes: Default::default() }
+    }
 }
 
 impl PartialEq for ConfigInner {
@@ -424,3 +550,44 @@ impl Deref for EglConfig {
         &self.0
     }
 }
+
+/// Convert the [`Api`] into the `EGL_RENDERABLE_TYPE` style bitmask.
+///
+/// [`Api::OPENVG`] is only reported, thus it's never used for matching.
+fn api_to_egl_bits(api: Api) -> u32 {
+    let mut raw_api = 0;
+    if api.contains(Api::GLES1) {
+        raw_api |= egl::OPENGL_ES_BIT;
+    }
+    if api.contains(Api::GLES2) {
+        raw_api |= egl::OPENGL_ES2_BIT;
+    }
+    if api.contains(Api::GLES3) {
+        raw_api |= egl::OPENGL_ES3_BIT;
+    }
+    if api.contains(Api::OPENGL) {
+        raw_api |= egl::OPENGL_BIT;
+    }
+    raw_api
+}
+
+/// Convert the `EGL_RENDERABLE_TYPE` style bitmask into the [`Api`].
+fn api_from_egl_bits(raw_api: u32) -> Api {
+    let mut api = Api::empty();
+    if raw_api & egl::OPENGL_BIT != 0 {
+        api.insert(Api::OPENGL);
+    }
+    if raw_api & egl::OPENGL_ES_BIT != 0 {
+        api.insert(Api::GLES1);
+    }
+    if raw_api & egl::OPENGL_ES2_BIT != 0 {
+        api.insert(Api::GLES2);
+    }
+    if raw_api & egl::OPENGL_ES3_BIT != 0 {
+        api.insert(Api::GLES3);
+    }
+    if raw_api & egl::OPENVG_BIT != 0 {
+        api.insert(Api::OPENVG);
+    }
+    api
+}
@@ -4,16 +4,16 @@ use std::fmt;
 use std::marker::PhantomData;
 use std::ops::Deref;
 
-use glutin_egl_sys::egl::types::{EGLenum, EGLint};
+use glutin_egl_sys::egl::types::{EGLDisplay, EGLSurface, EGLenum, EGLint};
 use glutin_egl_sys::{egl, EGLContext};
 
 use crate::config::{Api, GetGlConfig};
 use crate::context::{
-    self, AsRawContext, ContextApi, ContextAttributes, GlProfile, Priority, RawContext, Robustness,
-    Version,
+    self, AsRawContext, ContextApi, ContextAttributes, CreationAttributes, DebugCallbackSlot,
+    GlProfile, Priority, RawContext, ReleaseBehavior, Robustness, Version,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
-use crate::error::{ErrorKind, Result};
+use crate::error::{Error, ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::SurfaceTypeTrait;
@@ -27,11 +27,22 @@ impl Display {
         &self,
         config: &Config,
         context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext> {
+        context_attributes.with_robustness_fallback(|context_attributes| unsafe {
+            self.create_context_with_attributes(config, context_attributes)
+        })
+    }
+
+    unsafe fn create_context_with_attributes(
+        &self,
+        config: &Config,
+        context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
         let mut attrs = Vec::<EGLint>::new();
 
         let supports_opengl = self.inner.version > Version::new(1, 3);
-        let config_api = config.api();
+        // The context without config could be used with any api.
+        let config_api = if config.is_no_config() { Api::all() } else { config.api() };
 
         let (api, mut version) = match context_attributes.api {
             api @ Some(ContextApi::OpenGl(_)) | api @ None
@@ -56,7 +67,9 @@ impl Display {
         };
 
         let is_one_five = self.inner.version >= Version::new(1, 5);
-        if is_one_five || self.inner.display_extensions.contains("EGL_KHR_create_context") {
+        let supports_create_context =
+            is_one_five || self.inner.display_extensions.contains("EGL_KHR_create_context");
+        if supports_create_context {
             let mut flags = 0;
 
             // Add profile for the OpenGL Api.
@@ -92,12 +105,17 @@ impl Display {
                     attrs.push(egl::TRUE as EGLint);
                     requested_no_error = true;
                 },
-                Robustness::RobustLoseContextOnReset if has_robustsess => {
+                Robustness::RobustLoseContextOnReset | Robustness::TryRobustLoseContextOnReset
+                    if has_robustsess =>
+                {
                     attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
                     attrs.push(egl::LOSE_CONTEXT_ON_RESET as EGLint);
                     flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR;
                 },
-                Robustness::RobustNoResetNotification if has_robustsess => {
+                Robustness::RobustNoResetNotification
+                | Robustness::TryRobustNoResetNotification
+                    if has_robustsess =>
+                {
                     attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
                     attrs.push(egl::NO_RESET_NOTIFICATION as EGLint);
                     flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR;
@@ -112,21 +130,40 @@ impl Display {
             if context_attributes.debug && is_one_five && !requested_no_error {
                 attrs.push(egl::CONTEXT_OPENGL_DEBUG as EGLint);
                 attrs.push(egl::TRUE as EGLint);
+            } else if context_attributes.debug {
+                context_attributes.ignore_attribute("debug context is not supported")?;
             }
 
             if flags != 0 {
                 attrs.push(egl::CONTEXT_FLAGS_KHR as EGLint);
                 attrs.push(flags as EGLint);
             }
-        } else if self.inner.version >= Version::new(1, 3) {
+        } else {
+            // The profile can't be passed without `EGL_KHR_create_context`, so the driver
+            // would pick one on its own.
+            if context_attributes.profile.is_some() {
+                context_attributes.ignore_attribute(
+                    "EGL_KHR_create_context is required to request the OpenGL profile",
+                )?;
+            }
+
+            if context_attributes.debug || context_attributes.robustness != Robustness::NotRobust {
+                context_attributes.ignore_attribute("EGL_KHR_create_context is not supported")?;
+            }
+
             // EGL 1.3 uses that to indicate client version instead of major/minor. The
             // constant is the same as `CONTEXT_MAJOR_VERSION`.
-            if let Some(version) = version {
+            if let Some(version) = version.filter(|_| self.inner.version >= Version::new(1, 3)) {
                 attrs.push(egl::CONTEXT_CLIENT_VERSION as EGLint);
                 attrs.push(version.major as EGLint);
             }
         }
 
+        // EGL has no way to request the release behavior.
+        if context_attributes.release_behavior != ReleaseBehavior::Flush {
+            context_attributes.ignore_attribute("context release behavior is not supported")?;
+        }
+
         if let Some(priority) = context_attributes.priority.filter(|_| {
             let extensions = &self.inner.display_extensions;
 
@@ -150,6 +187,8 @@ impl Display {
                     if self.inner.display_extensions.contains("EGL_NV_context_priority_realtime") {
                         egl::CONTEXT_PRIORITY_REALTIME_NV
                     } else {
+                        context_attributes
+                            .ignore_attribute("realtime context priority is not supported")?;
                         egl::CONTEXT_PRIORITY_HIGH_IMG
                     }
                 },
@@ -157,6 +196,14 @@ impl Display {
 
             attrs.push(egl::CONTEXT_PRIORITY_LEVEL_IMG as EGLint);
             attrs.push(priority as EGLint);
+        } else if context_attributes.priority.is_some() {
+            context_attributes.ignore_attribute("context priority is not supported")?;
+        }
+
+        if context_attributes.protected {
+            self.inner.check_protected_content()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLint);
+            attrs.push(egl::TRUE as EGLint);
         }
 
         attrs.push(egl::NONE as EGLint);
@@ -179,6 +226,10 @@ impl Display {
                 return Err(super::check_error().err().unwrap());
             }
 
+            if shared_context != egl::NO_CONTEXT {
+                self.check_shared_context(shared_context, api)?;
+            }
+
             let config = config.clone();
             let context = self.inner.egl.CreateContext(
                 *self.inner.raw,
@@ -191,13 +242,116 @@ impl Display {
                 return Err(super::check_error().err().unwrap());
             }
 
-            let inner =
-                ContextInner { display: self.clone(), config, raw: EglContext(context), api };
+            // EGL 1.3 only takes the major version and may give a lower one.
+            let requested_version = match context_attributes.api {
+                Some(ContextApi::Gles(Some(version))) => Some(version),
+                _ => None,
+            };
+            if let Some(requested) =
+                requested_version.filter(|_| !context_attributes.allow_version_downgrade)
+            {
+                let mut major = 0;
+                let queried = self.inner.egl.QueryContext(
+                    *self.inner.raw,
+                    context,
+                    egl::CONTEXT_CLIENT_VERSION as EGLint,
+                    &mut major,
+                ) != egl::FALSE;
+                if queried && major < requested.major as EGLint {
+                    self.inner.egl.DestroyContext(*self.inner.raw, context);
+                    return Err(Error::new(
+                        None,
+                        Some(format!(
+                            "requested OpenGL ES {}.{}, but got {major}.x",
+                            requested.major, requested.minor
+                        )),
+                        ErrorKind::BadContext,
+                    ));
+                }
+            }
+
+            let inner = ContextInner {
+                display: self.clone(),
+                config,
+                raw: EglContext(context),
+                api,
+                // The robustness can't be requested without `EGL_KHR_create_context`.
+                robustness: if supports_create_context {
+                    context_attributes.robustness
+                } else {
+                    Robustness::NotRobust
+                },
+                attributes: CreationAttributes::new(context_attributes),
+                debug_callback: Default::default(),
+            };
             Ok(NotCurrentContext::new(inner))
         }
     }
 }
 
+impl Display {
+    /// Create the context which is not bound to any particular config.
+    ///
+    /// Such context could be made current with surfaces created from the
+    /// different, but compatible, configs. This requires
+    /// `EGL_KHR_no_config_context`.
+    ///
+    /// The config returned by [`GetGlConfig::config`] for such context doesn't
+    /// name any `EGLConfig`, thus querying its attributes is meaningless.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    pub unsafe fn create_context_no_config(
+        &self,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext> {
+        if !self.inner.display_extensions.contains("EGL_KHR_no_config_context") {
+            return Err(ErrorKind::NotSupported("contexts without config are not supported").into());
+        }
+
+        let config = Config::no_config(self);
+        unsafe { self.create_context(&config, context_attributes) }
+    }
+
+    /// Check that the `shared_context` could be shared with the context of
+    /// the given `api` created on this display.
+    ///
+    /// The context must belong to the same `EGLDisplay` and use the same
+    /// client API to be shared.
+    unsafe fn check_shared_context(&self, shared_context: EGLContext, api: EGLenum) -> Result<()> {
+        let mut client_type = 0;
+        let queried = unsafe {
+            self.inner.egl.QueryContext(
+                *self.inner.raw,
+                shared_context,
+                egl::CONTEXT_CLIENT_TYPE as EGLint,
+                &mut client_type,
+            )
+        };
+
+        if queried == egl::FALSE {
+            let err = super::check_error().err().unwrap();
+            return Err(Error::new(
+                None,
+                Some("the shared context doesn't belong to a compatible display".into()),
+                ErrorKind::BadContext,
+            )
+            .with_source(err));
+        }
+
+        if client_type as EGLenum != api {
+            return Err(Error::new(
+                None,
+                Some("the shared context uses a different client api".into()),
+                ErrorKind::BadMatch,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// A wrapper around `EGLContext` that is known to be not current.
 #[derive(Debug)]
 pub struct NotCurrentContext {
@@ -249,6 +403,10 @@ impl GlContext for NotCurrentContext {
     fn priority(&self) -> Priority {
         self.inner.priority()
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl GetGlConfig for NotCurrentContext {
@@ -327,6 +485,10 @@ impl GlContext for PossiblyCurrentContext {
     fn priority(&self) -> Priority {
         self.inner.priority()
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl GetGlConfig for PossiblyCurrentContext {
@@ -353,16 +515,92 @@ impl AsRawContext for PossiblyCurrentContext {
 
 impl Sealed for PossiblyCurrentContext {}
 
+impl PossiblyCurrentContext {
+    pub(crate) fn context_attributes(&self) -> &ContextAttributes {
+        &self.inner.attributes
+    }
+
+    pub(crate) fn debug_callback(&self) -> &DebugCallbackSlot {
+        &self.inner.debug_callback
+    }
+
+    /// Whether the context is still usable, checked by querying its config
+    /// with `eglQueryContext`.
+    pub fn is_valid(&self) -> bool {
+        self.inner.query_attribute(egl::CONFIG_ID as EGLint).is_some()
+    }
+
+    /// Save the context current on the thread for the Api of this context.
+    pub(crate) fn save_current(&self) -> CurrentState {
+        let egl = &self.inner.display.inner.egl;
+        unsafe {
+            let api = egl.QueryAPI();
+            self.inner.bind_api();
+            let state = CurrentState {
+                api,
+                display: egl.GetCurrentDisplay(),
+                context: egl.GetCurrentContext(),
+                draw: egl.GetCurrentSurface(egl::DRAW as EGLint),
+                read: egl.GetCurrentSurface(egl::READ as EGLint),
+            };
+            egl.BindAPI(api);
+            state
+        }
+    }
+
+    /// Restore the state saved with [`Self::save_current`].
+    pub(crate) fn restore_current(&self, state: &CurrentState) -> Result<()> {
+        let egl = &self.inner.display.inner.egl;
+        let result = if state.context == egl::NO_CONTEXT {
+            self.inner.make_not_current()
+        } else {
+            unsafe {
+                self.inner.bind_api();
+                if egl.MakeCurrent(state.display, state.draw, state.read, state.context)
+                    == egl::FALSE
+                {
+                    super::check_error()
+                } else {
+                    Ok(())
+                }
+            }
+        };
+
+        unsafe {
+            egl.BindAPI(state.api);
+        }
+
+        result
+    }
+}
+
+/// The current context of the thread saved by the [`CurrentContextGuard`].
+///
+/// [`CurrentContextGuard`]: crate::context::CurrentContextGuard
+#[derive(Debug)]
+pub(crate) struct CurrentState {
+    api: EGLenum,
+    display: EGLDisplay,
+    context: EGLContext,
+    draw: EGLSurface,
+    read: EGLSurface,
+}
+
 pub(crate) struct ContextInner {
     display: Display,
     config: Config,
     raw: EglContext,
     api: egl::types::EGLenum,
+    robustness: Robustness,
+    attributes: CreationAttributes,
+    debug_callback: DebugCallbackSlot,
 }
 
 impl ContextInner {
     fn make_current_surfaceless(&self) -> Result<()> {
         unsafe {
+            self.bind_api();
+
             if self.display.inner.egl.MakeCurrent(
                 *self.display.inner.raw,
                 egl::NO_SURFACE,
@@ -383,6 +621,10 @@ impl ContextInner {
         surface_read: &Surface<T>,
     ) -> Result<()> {
         unsafe {
+            // The context is bound to the current Api of the thread, thus it must match the
+            // Api of the context, which could be different when mixing GL and GLES contexts.
+            self.bind_api();
+
             let draw = surface_draw.raw;
             let read = surface_read.raw;
             if self.display.inner.egl.MakeCurrent(*self.display.inner.raw, draw, read, *self.raw)
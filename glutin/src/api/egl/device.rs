@@ -1,11 +1,15 @@
 //! Everything related to `EGLDevice`.
 
 use std::collections::HashSet;
+#[cfg(windows)]
+use std::ffi;
 use std::ffi::CStr;
 use std::path::Path;
 use std::ptr;
 
 use glutin_egl_sys::egl;
+#[cfg(windows)]
+use glutin_egl_sys::egl::types::EGLAttrib;
 use glutin_egl_sys::egl::types::EGLDeviceEXT;
 
 use crate::error::{ErrorKind, Result};
@@ -169,6 +173,58 @@ impl Device {
             .map(Path::new)
     }
 
+    /// Get the `ID3D11Device` used by the ANGLE device.
+    ///
+    /// Requires the `EGL_ANGLE_device_d3d` extension and ANGLE using the D3D11
+    /// renderer, otherwise [`None`] is returned.
+    #[cfg(windows)]
+    pub fn d3d11_device(&self) -> Option<*mut ffi::c_void> {
+        if !self.extensions.contains("EGL_ANGLE_device_d3d") {
+            return None;
+        }
+
+        let egl = super::EGL.as_ref().unwrap();
+        let mut device: EGLAttrib = 0;
+        // SAFETY: The attribute is valid because the extension is present.
+        let result = unsafe {
+            egl.QueryDeviceAttribEXT(self.inner, egl::D3D11_DEVICE_ANGLE as _, &mut device)
+        };
+
+        if result == egl::FALSE || device == 0 {
+            None
+        } else {
+            Some(device as *mut ffi::c_void)
+        }
+    }
+
+    /// Check whether the D3D11 device used by ANGLE was removed, e.g. due
+    /// to the driver timeout (TDR).
+    ///
+    /// Returns [`None`] when the device can't be queried.
+    #[cfg(windows)]
+    pub(crate) fn is_d3d11_device_removed(&self) -> Option<bool> {
+        /// The index of `ID3D11Device::GetDeviceRemovedReason` in the vtable,
+        /// following the 3 `IUnknown` and 36 preceding `ID3D11Device` methods.
+        const GET_DEVICE_REMOVED_REASON: usize = 39;
+
+        type GetDeviceRemovedReason = unsafe extern "system" fn(*mut ffi::c_void) -> i32;
+
+        let device = self.d3d11_device()?;
+
+        // SAFETY: ANGLE returns the valid `ID3D11Device` for the
+        // `EGL_D3D11_DEVICE_ANGLE`, which is a COM object starting with the
+        // pointer to its vtable.
+        let hresult = unsafe {
+            let vtable = *(device as *const *const usize);
+            let get_device_removed_reason: GetDeviceRemovedReason =
+                std::mem::transmute(*vtable.add(GET_DEVICE_REMOVED_REASON));
+            get_device_removed_reason(device)
+        };
+
+        // Anything but `S_OK` means that the device was removed.
+        Some(hresult != 0)
+    }
+
     /// # Safety
     /// The caller must pass  a valid `egl_device` pointer and must ensure that
     /// `name` is valid for this device, i.e. by guaranteeing that the
@@ -17,7 +17,9 @@ use raw_window_handle::{RawDisplayHandle, XlibDisplayHandle};
 
 use crate::config::ConfigTemplate;
 use crate::context::Version;
-use crate::display::{AsRawDisplay, DisplayFeatures, GetDisplayExtensions, RawDisplay};
+use crate::display::{
+    AsRawDisplay, DeviceResetStatus, DisplayFeatures, GetDisplayExtensions, RawDisplay,
+};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
@@ -75,7 +77,120 @@ impl Display {
                 }
             })?;
 
-        Self::initialize_display(egl, display, Some(raw_display))
+        Self::initialize_display(egl, display, Some(NativeDisplay::new(raw_display)))
+    }
+
+    /// Create EGL display for the given `platform`, instead of the one
+    /// inferred from the `raw_display`.
+    ///
+    /// This could be used to e.g. force the [`EglPlatform::SurfacelessMesa`]
+    /// when running with the X11 display. The native display passed to EGL is
+    /// still taken from the `raw_display`, except for the surfaceless
+    /// platform, which always uses `EGL_DEFAULT_DISPLAY`.
+    ///
+    /// The [`ErrorKind::NotSupported`] is returned when the `raw_display`
+    /// doesn't match the `platform`, e.g. the Xlib display for the
+    /// [`EglPlatform::Wayland`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn with_platform(
+        raw_display: RawDisplayHandle,
+        platform: EglPlatform,
+    ) -> Result<Self> {
+        let egl = match EGL.as_ref() {
+            Some(egl) => egl,
+            None => return Err(ErrorKind::NotFound.into()),
+        };
+
+        let extensions = CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+
+        let (egl_platform, platform_extensions): (_, &[&str]) = match platform {
+            EglPlatform::X11 => {
+                (egl::PLATFORM_X11_KHR, &["EGL_KHR_platform_x11", "EGL_EXT_platform_x11"])
+            },
+            EglPlatform::Xcb => {
+                (egl::PLATFORM_XCB_EXT, &["EGL_EXT_platform_xcb", "EGL_MESA_platform_xcb"])
+            },
+            EglPlatform::Wayland => (
+                egl::PLATFORM_WAYLAND_KHR,
+                &["EGL_KHR_platform_wayland", "EGL_EXT_platform_wayland"],
+            ),
+            EglPlatform::Gbm => {
+                (egl::PLATFORM_GBM_KHR, &["EGL_KHR_platform_gbm", "EGL_MESA_platform_gbm"])
+            },
+            EglPlatform::Android => (egl::PLATFORM_ANDROID_KHR, &["EGL_KHR_platform_android"]),
+            EglPlatform::SurfacelessMesa => {
+                (egl::PLATFORM_SURFACELESS_MESA, &["EGL_MESA_platform_surfaceless"])
+            },
+        };
+
+        if !platform_extensions.iter().any(|extension| extensions.contains(extension)) {
+            return Err(
+                ErrorKind::NotSupported("the requested EGL platform is not supported").into()
+            );
+        }
+
+        // The screen attribute, passed for the X11 platforms.
+        let mut screen = None;
+        let native_display = match (platform, raw_display) {
+            (EglPlatform::SurfacelessMesa, _) => NativeDisplay::default_display(raw_display),
+            (EglPlatform::X11, RawDisplayHandle::Xlib(handle)) => {
+                screen = Some((egl::PLATFORM_X11_SCREEN_KHR, handle.screen));
+                NativeDisplay::new(raw_display)
+            },
+            (EglPlatform::Xcb, RawDisplayHandle::Xcb(handle)) => {
+                screen = Some((egl::PLATFORM_XCB_SCREEN_EXT, handle.screen));
+                NativeDisplay::new(raw_display)
+            },
+            (EglPlatform::Wayland, RawDisplayHandle::Wayland(_))
+            | (EglPlatform::Gbm, RawDisplayHandle::Gbm(_))
+            | (EglPlatform::Android, RawDisplayHandle::Android(_)) => {
+                NativeDisplay::new(raw_display)
+            },
+            _ => {
+                return Err(ErrorKind::NotSupported(
+                    "the display handle doesn't match the requested EGL platform",
+                )
+                .into())
+            },
+        };
+        let display = native_display.as_native_display();
+
+        let display = if egl.GetPlatformDisplay.is_loaded() {
+            let mut attrs = Vec::<EGLAttrib>::with_capacity(5);
+            if let Some((attr, screen)) = screen {
+                attrs.push(attr as EGLAttrib);
+                attrs.push(screen as EGLAttrib);
+            }
+
+            Self::get_platform_display_with_reference(
+                attrs,
+                DISPLAY_REFERENCE_ATTRIBS,
+                |attrs| unsafe { egl.GetPlatformDisplay(egl_platform, display as *mut _, attrs) },
+            )
+            .map(EglDisplay::Khr)?
+        } else if egl.GetPlatformDisplayEXT.is_loaded() {
+            let mut attrs = Vec::<EGLint>::with_capacity(5);
+            if let Some((attr, screen)) = screen {
+                attrs.push(attr as EGLint);
+                attrs.push(screen as EGLint);
+            }
+
+            Self::get_platform_display_with_reference(
+                attrs,
+                DISPLAY_REFERENCE_ATTRIBS_EXT,
+                |attrs| unsafe {
+                    egl.GetPlatformDisplayEXT(egl_platform, display as *mut _, attrs)
+                },
+            )
+            .map(EglDisplay::Ext)?
+        } else {
+            return Err(ErrorKind::NotSupported("eglGetPlatformDisplay is not supported").into());
+        };
+
+        Self::initialize_display(egl, display, Some(native_display))
     }
 
     /// Create an EGL display using the specified device.
@@ -227,6 +342,36 @@ impl Display {
         Device::from_ptr(self.inner.egl, device)
     }
 
+    /// Check whether the D3D11 device backing the ANGLE display was reset,
+    /// e.g. due to the driver timeout (TDR).
+    ///
+    /// This requires `EGL_ANGLE_device_d3d` on Windows, otherwise
+    /// [`DeviceResetStatus::Unknown`] is returned.
+    pub fn check_device_reset(&self) -> DeviceResetStatus {
+        #[cfg(windows)]
+        match self.device().ok().and_then(|device| device.is_d3d11_device_removed()) {
+            Some(true) => DeviceResetStatus::Reset,
+            Some(false) => DeviceResetStatus::NoReset,
+            None => DeviceResetStatus::Unknown,
+        }
+
+        #[cfg(not(windows))]
+        DeviceResetStatus::Unknown
+    }
+
+    /// The `EGLNativeDisplayType` the display was created from, like the
+    /// `wl_display` on Wayland or the Xlib `Display` on X11.
+    ///
+    /// The [`egl::DEFAULT_DISPLAY`] is returned when the platform's default
+    /// display was used, and `None` when the display wasn't created from
+    /// the native display, e.g. with [`Display::with_device`].
+    ///
+    /// The pointer is owned by the caller of [`Display::new`] and is only
+    /// valid as long as the native display it was obtained from.
+    pub fn egl_native_display(&self) -> Option<egl::NativeDisplayType> {
+        self.inner._native_display.map(|display| display.as_native_display())
+    }
+
     /// Get a reference to the initialized EGL API.
     pub fn egl(&self) -> &'static Egl {
         self.inner.egl
@@ -292,40 +437,12 @@ impl Display {
             },
         };
 
-        // Push at the end so we can pop it on failure
-        let mut has_display_reference = extensions.contains("EGL_KHR_display_reference");
-        if has_display_reference {
-            attrs.push(egl::TRACK_REFERENCES_KHR as _);
-            attrs.push(egl::TRUE as _);
-        }
-
-        // Push `egl::NONE` to terminate the list.
-        attrs.push(egl::NONE as EGLAttrib);
-
-        // NOTE: This fallback is needed because libglvnd advertises client extensions
-        // if at least one vendor library supports them. This leads to creation
-        // failures for the vendor libraries not supporting
-        // EGL_KHR_display_reference. Also according to the spec creation is allowed
-        // to fail with EGL_KHR_display_reference set to EGL_TRUE even if
-        // EGL_KHR_display_reference is advertised in the client extension
-        // string, so just always try creation without EGL_KHR_display_reference
-        // if it failed using it.
-        let platform_display = loop {
-            match Self::check_display_error(unsafe {
-                egl.GetPlatformDisplay(platform, display as *mut _, attrs.as_ptr())
-            }) {
-                Err(_) if has_display_reference => {
-                    attrs.pop();
-                    attrs.pop();
-                    attrs.pop();
-                    attrs.push(egl::NONE as EGLAttrib);
-                    has_display_reference = false;
-                },
-                platform_display => break platform_display,
-            }
-        };
-
-        platform_display.map(EglDisplay::Khr)
+        Self::get_platform_display_with_reference(
+            attrs,
+            DISPLAY_REFERENCE_ATTRIBS,
+            |attrs| unsafe { egl.GetPlatformDisplay(platform, display as *mut _, attrs) },
+        )
+        .map(EglDisplay::Khr)
     }
 
     fn get_platform_display_ext(egl: &Egl, display: RawDisplayHandle) -> Result<EglDisplay> {
@@ -392,38 +509,11 @@ impl Display {
             },
         };
 
-        // Push at the end so we can pop it on failure
-        let mut has_display_reference = extensions.contains("EGL_KHR_display_reference");
-        if has_display_reference {
-            attrs.push(egl::TRACK_REFERENCES_KHR as _);
-            attrs.push(egl::TRUE as _);
-        }
-
-        // Push `egl::NONE` to terminate the list.
-        attrs.push(egl::NONE as EGLint);
-
-        // NOTE: This fallback is needed because libglvnd advertises client extensions
-        // if at least one vendor library supports them. This leads to creation
-        // failures for the vendor libraries not supporting
-        // EGL_KHR_display_reference. Also according to the spec creation is allowed
-        // to fail with EGL_KHR_display_reference set to EGL_TRUE even if
-        // EGL_KHR_display_reference is advertised in the client extension
-        // string, so just always try creation without EGL_KHR_display_reference
-        // if it failed using it.
-        let platform_display = loop {
-            match Self::check_display_error(unsafe {
-                egl.GetPlatformDisplayEXT(platform, display as *mut _, attrs.as_ptr())
-            }) {
-                Err(_) if has_display_reference => {
-                    attrs.pop();
-                    attrs.pop();
-                    attrs.pop();
-                    attrs.push(egl::NONE as EGLint);
-                    has_display_reference = false;
-                },
-                platform_display => break platform_display,
-            }
-        };
+        let platform_display = Self::get_platform_display_with_reference(
+            attrs,
+            DISPLAY_REFERENCE_ATTRIBS_EXT,
+            |attrs| unsafe { egl.GetPlatformDisplayEXT(platform, display as *mut _, attrs) },
+        );
 
         platform_display.map(|display| {
             if legacy {
@@ -471,7 +561,8 @@ impl Display {
         // Extract features.
         let mut supported_features = DisplayFeatures::CREATE_ES_CONTEXT
             | DisplayFeatures::MULTISAMPLING_PIXEL_FORMATS
-            | DisplayFeatures::SWAP_CONTROL;
+            | DisplayFeatures::SWAP_CONTROL
+            | DisplayFeatures::SEPARATE_READ_SURFACE;
 
         supported_features.set(
             DisplayFeatures::FLOAT_PIXEL_FORMAT,
@@ -495,6 +586,47 @@ impl Display {
         supported_features
     }
 
+    /// Create the platform display with `get_platform_display` from the
+    /// `attrs`, requesting the `EGL_KHR_display_reference` when it's
+    /// available.
+    ///
+    /// The `EGL_TRACK_REFERENCES_KHR`, `EGL_TRUE`, and `EGL_NONE` are passed in
+    /// the attribute type, like [`DISPLAY_REFERENCE_ATTRIBS`].
+    fn get_platform_display_with_reference<T: Copy>(
+        mut attrs: Vec<T>,
+        [track_references, egl_true, none]: [T; 3],
+        get_platform_display: impl Fn(*const T) -> EGLDisplay,
+    ) -> Result<EGLDisplay> {
+        let extensions = CLIENT_EXTENSIONS.get().unwrap();
+
+        // Push at the end so we can pop it on failure
+        let has_display_reference = extensions.contains("EGL_KHR_display_reference");
+        if has_display_reference {
+            attrs.push(track_references);
+            attrs.push(egl_true);
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(none);
+
+        // NOTE: This fallback is needed because libglvnd advertises client extensions
+        // if at least one vendor library supports them. This leads to creation
+        // failures for the vendor libraries not supporting
+        // EGL_KHR_display_reference. Also according to the spec creation is allowed
+        // to fail with EGL_KHR_display_reference set to EGL_TRUE even if
+        // EGL_KHR_display_reference is advertised in the client extension
+        // string, so just always try creation without EGL_KHR_display_reference
+        // if it failed using it.
+        match Self::check_display_error(get_platform_display(attrs.as_ptr())) {
+            Err(_) if has_display_reference => {
+                attrs.truncate(attrs.len() - 3);
+                attrs.push(none);
+                Self::check_display_error(get_platform_display(attrs.as_ptr()))
+            },
+            platform_display => platform_display,
+        }
+    }
+
     fn check_display_error(display: EGLDisplay) -> Result<EGLDisplay> {
         if display == egl::NO_DISPLAY {
             // XXX the specification is a bit vague here, so fallback instead of hard
@@ -510,7 +642,7 @@ impl Display {
     fn initialize_display(
         egl: &'static Egl,
         display: EglDisplay,
-        raw_display_handle: Option<RawDisplayHandle>,
+        native_display: Option<NativeDisplay>,
     ) -> Result<Self> {
         let version = unsafe {
             let (mut major, mut minor) = (0, 0);
@@ -548,7 +680,7 @@ impl Display {
         let inner = Arc::new(DisplayInner {
             egl,
             raw: display,
-            _native_display: raw_display_handle.map(NativeDisplay),
+            _native_display: native_display,
             version,
             display_extensions,
             features,
@@ -651,6 +783,15 @@ pub(crate) struct DisplayInner {
 }
 
 impl DisplayInner {
+    /// Fail when the protected content isn't supported by the display.
+    pub(crate) fn check_protected_content(&self) -> Result<()> {
+        if self.display_extensions.contains("EGL_EXT_protected_content") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("protected content is not supported").into())
+        }
+    }
+
     fn uses_display_reference(&self) -> bool {
         if !CLIENT_EXTENSIONS.get().unwrap().contains("EGL_KHR_display_reference") {
             return false;
@@ -745,17 +886,87 @@ impl Drop for DisplayInner {
     }
 }
 
+/// The `EGL_TRACK_REFERENCES_KHR`, `EGL_TRUE`, and `EGL_NONE` passed to
+/// `eglGetPlatformDisplay`.
+const DISPLAY_REFERENCE_ATTRIBS: [EGLAttrib; 3] =
+    [egl::TRACK_REFERENCES_KHR as EGLAttrib, egl::TRUE as EGLAttrib, egl::NONE as EGLAttrib];
+
+/// The [`DISPLAY_REFERENCE_ATTRIBS`] passed to `eglGetPlatformDisplayEXT`.
+const DISPLAY_REFERENCE_ATTRIBS_EXT: [EGLint; 3] =
+    [egl::TRACK_REFERENCES_KHR as EGLint, egl::TRUE as EGLint, egl::NONE as EGLint];
+
+/// The EGL platform to create the [`Display`] for with
+/// [`Display::with_platform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EglPlatform {
+    /// `EGL_PLATFORM_X11_KHR`, requires the Xlib display handle.
+    X11,
+
+    /// `EGL_PLATFORM_XCB_EXT`, requires the XCB display handle.
+    Xcb,
+
+    /// `EGL_PLATFORM_WAYLAND_KHR`, requires the Wayland display handle.
+    Wayland,
+
+    /// `EGL_PLATFORM_GBM_KHR`, requires the GBM display handle.
+    Gbm,
+
+    /// `EGL_PLATFORM_ANDROID_KHR`.
+    Android,
+
+    /// `EGL_PLATFORM_SURFACELESS_MESA`, which doesn't need the native
+    /// display and supports only pbuffers and surfaceless contexts.
+    SurfacelessMesa,
+}
+
 #[derive(Debug, Clone, Copy)]
-pub(crate) struct NativeDisplay(RawDisplayHandle);
+pub(crate) struct NativeDisplay {
+    raw: RawDisplayHandle,
+
+    /// The `EGL_DEFAULT_DISPLAY` was passed instead of the `raw` display.
+    default_display: bool,
+}
 
 unsafe impl Send for NativeDisplay {}
 unsafe impl Sync for NativeDisplay {}
 
+impl NativeDisplay {
+    fn new(raw: RawDisplayHandle) -> Self {
+        Self { raw, default_display: false }
+    }
+
+    /// The display created with `EGL_DEFAULT_DISPLAY` for the `raw` display,
+    /// e.g. on the surfaceless platform.
+    fn default_display(raw: RawDisplayHandle) -> Self {
+        Self { raw, default_display: true }
+    }
+
+    /// The native display passed to `eglGetPlatformDisplay` and its
+    /// alternatives.
+    fn as_native_display(&self) -> egl::NativeDisplayType {
+        if self.default_display {
+            return egl::DEFAULT_DISPLAY as *mut _;
+        }
+
+        match self.raw {
+            RawDisplayHandle::Wayland(handle) => handle.display.as_ptr(),
+            RawDisplayHandle::Xlib(handle) => {
+                handle.display.map_or(egl::DEFAULT_DISPLAY as *mut _, |d| d.as_ptr())
+            },
+            RawDisplayHandle::Xcb(handle) => {
+                handle.connection.map_or(egl::DEFAULT_DISPLAY as *mut _, |c| c.as_ptr())
+            },
+            RawDisplayHandle::Gbm(handle) => handle.gbm_device.as_ptr(),
+            _ => egl::DEFAULT_DISPLAY as *mut _,
+        }
+    }
+}
+
 impl Deref for NativeDisplay {
     type Target = RawDisplayHandle;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.raw
     }
 }
 
@@ -0,0 +1,260 @@
+//! Everything related to `EGLImage`.
+
+use std::ffi;
+use std::fmt;
+use std::num::NonZeroU32;
+use std::os::fd::{AsRawFd, BorrowedFd};
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLImageKHR, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::display::Display;
+
+/// The planes attributes for each of the up to four dma-buf planes.
+const PLANE_ATTRIBUTES: [[egl::types::EGLenum; 5]; 4] = [
+    [
+        egl::DMA_BUF_PLANE0_FD_EXT,
+        egl::DMA_BUF_PLANE0_OFFSET_EXT,
+        egl::DMA_BUF_PLANE0_PITCH_EXT,
+        egl::DMA_BUF_PLANE0_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE0_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE1_FD_EXT,
+        egl::DMA_BUF_PLANE1_OFFSET_EXT,
+        egl::DMA_BUF_PLANE1_PITCH_EXT,
+        egl::DMA_BUF_PLANE1_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE1_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE2_FD_EXT,
+        egl::DMA_BUF_PLANE2_OFFSET_EXT,
+        egl::DMA_BUF_PLANE2_PITCH_EXT,
+        egl::DMA_BUF_PLANE2_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE2_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE3_FD_EXT,
+        egl::DMA_BUF_PLANE3_OFFSET_EXT,
+        egl::DMA_BUF_PLANE3_PITCH_EXT,
+        egl::DMA_BUF_PLANE3_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE3_MODIFIER_HI_EXT,
+    ],
+];
+
+/// A single plane of the dma-buf to import with [`Display::import_dma_buf`].
+#[derive(Debug, Clone, Copy)]
+pub struct DmaBufPlane<'a> {
+    /// The dma-buf file descriptor of the plane.
+    ///
+    /// EGL doesn't take the ownership of it, so it could be closed once the
+    /// image is created.
+    pub fd: BorrowedFd<'a>,
+
+    /// The offset of the plane in the dma-buf in bytes.
+    pub offset: u32,
+
+    /// The stride of the plane in bytes.
+    pub pitch: u32,
+}
+
+impl Display {
+    /// Import the dma-buf, e.g. exported from the Vulkan memory, as
+    /// [`Image`].
+    ///
+    /// The `fourcc` is the `DRM_FORMAT_*` of the buffer, and the `modifier`
+    /// its `DRM_FORMAT_MOD_*`, where `None` lets the driver assume the
+    /// implicit one. The resulting image could be bound to the GL texture
+    /// with `glEGLImageTargetTexture2DOES`.
+    ///
+    /// This requires `EGL_KHR_image_base` and `EGL_EXT_image_dma_buf_import`,
+    /// passing the `modifier` requires
+    /// `EGL_EXT_image_dma_buf_import_modifiers` in addition.
+    ///
+    /// # Safety
+    ///
+    /// The `planes` must describe the memory layout of the dma-buf, since
+    /// the driver may not validate it.
+    pub unsafe fn import_dma_buf(
+        &self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        fourcc: u32,
+        modifier: Option<u64>,
+        planes: &[DmaBufPlane<'_>],
+    ) -> Result<Image> {
+        let extensions = &self.inner.display_extensions;
+        if !extensions.contains("EGL_KHR_image_base")
+            || !extensions.contains("EGL_EXT_image_dma_buf_import")
+        {
+            return Err(ErrorKind::NotSupported("importing dma-buf is not supported").into());
+        }
+
+        if modifier.is_some() && !extensions.contains("EGL_EXT_image_dma_buf_import_modifiers") {
+            return Err(ErrorKind::NotSupported("dma-buf modifiers are not supported").into());
+        }
+
+        if planes.is_empty() || planes.len() > PLANE_ATTRIBUTES.len() {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        let mut attrs = Vec::<EGLint>::with_capacity(7 + planes.len() * 10);
+        attrs.push(egl::WIDTH as EGLint);
+        attrs.push(width.get() as EGLint);
+        attrs.push(egl::HEIGHT as EGLint);
+        attrs.push(height.get() as EGLint);
+        attrs.push(egl::LINUX_DRM_FOURCC_EXT as EGLint);
+        attrs.push(fourcc as EGLint);
+
+        for (plane, [fd, offset, pitch, modifier_lo, modifier_hi]) in
+            planes.iter().zip(PLANE_ATTRIBUTES)
+        {
+            attrs.push(fd as EGLint);
+            attrs.push(plane.fd.as_raw_fd());
+            attrs.push(offset as EGLint);
+            attrs.push(plane.offset as EGLint);
+            attrs.push(pitch as EGLint);
+            attrs.push(plane.pitch as EGLint);
+
+            if let Some(modifier) = modifier {
+                attrs.push(modifier_lo as EGLint);
+                attrs.push(modifier as u32 as EGLint);
+                attrs.push(modifier_hi as EGLint);
+                attrs.push((modifier >> 32) as u32 as EGLint);
+            }
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLint);
+
+        let raw = unsafe {
+            self.inner.egl.CreateImageKHR(
+                *self.inner.raw,
+                egl::NO_CONTEXT,
+                egl::LINUX_DMA_BUF_EXT,
+                std::ptr::null(),
+                attrs.as_ptr(),
+            )
+        };
+
+        if raw == egl::NO_IMAGE_KHR {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Image { display: self.clone(), raw })
+    }
+
+    /// Query the `DRM_FORMAT_*` fourcc codes which could be imported with
+    /// [`Display::import_dma_buf`].
+    ///
+    /// This requires `EGL_EXT_image_dma_buf_import_modifiers`, otherwise the
+    /// empty list is returned.
+    pub fn query_dma_buf_formats(&self) -> Vec<u32> {
+        if !self.inner.display_extensions.contains("EGL_EXT_image_dma_buf_import_modifiers") {
+            return Vec::new();
+        }
+
+        unsafe {
+            let mut num_formats = 0;
+            let egl = &self.inner.egl;
+            if egl.QueryDmaBufFormatsEXT(*self.inner.raw, 0, std::ptr::null_mut(), &mut num_formats)
+                == egl::FALSE
+            {
+                return Vec::new();
+            }
+
+            let mut formats = vec![0; num_formats.max(0) as usize];
+            if egl.QueryDmaBufFormatsEXT(
+                *self.inner.raw,
+                formats.len() as EGLint,
+                formats.as_mut_ptr(),
+                &mut num_formats,
+            ) == egl::FALSE
+            {
+                return Vec::new();
+            }
+
+            formats.truncate(num_formats.max(0) as usize);
+            formats.into_iter().map(|format| format as u32).collect()
+        }
+    }
+
+    /// Query the `DRM_FORMAT_MOD_*` modifiers supported for the `fourcc`
+    /// `DRM_FORMAT_*`, which could be passed to [`Display::import_dma_buf`].
+    ///
+    /// This requires `EGL_EXT_image_dma_buf_import_modifiers`, otherwise the
+    /// empty list is returned.
+    pub fn query_dma_buf_modifiers(&self, fourcc: u32) -> Vec<u64> {
+        if !self.inner.display_extensions.contains("EGL_EXT_image_dma_buf_import_modifiers") {
+            return Vec::new();
+        }
+
+        unsafe {
+            let mut num_modifiers = 0;
+            let egl = &self.inner.egl;
+            if egl.QueryDmaBufModifiersEXT(
+                *self.inner.raw,
+                fourcc as EGLint,
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+            {
+                return Vec::new();
+            }
+
+            let mut modifiers = vec![0; num_modifiers.max(0) as usize];
+            if egl.QueryDmaBufModifiersEXT(
+                *self.inner.raw,
+                fourcc as EGLint,
+                modifiers.len() as EGLint,
+                modifiers.as_mut_ptr(),
+                std::ptr::null_mut(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+            {
+                return Vec::new();
+            }
+
+            modifiers.truncate(num_modifiers.max(0) as usize);
+            modifiers
+        }
+    }
+}
+
+/// A wrapper around `EGLImage`, which is destroyed on drop.
+///
+/// The GL objects using the image keep its contents alive, thus it could be
+/// dropped once bound to them.
+pub struct Image {
+    display: Display,
+    raw: EGLImageKHR,
+}
+
+impl Image {
+    /// The raw `EGLImage` to pass to `glEGLImageTargetTexture2DOES` and
+    /// similar functions.
+    pub fn raw_image(&self) -> *const ffi::c_void {
+        self.raw
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroyImageKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
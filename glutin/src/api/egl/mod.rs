@@ -26,7 +26,11 @@ pub mod config;
 pub mod context;
 pub mod device;
 pub mod display;
+#[cfg(unix)]
+pub mod image;
+pub mod stream;
 pub mod surface;
+pub mod sync;
 
 // WARNING: If this implementation is ever changed to unload or replace the
 // library, note that public API functions currently retirm `&'static str`ings
@@ -120,6 +124,6 @@ fn check_error() -> Result<()> {
             _ => ErrorKind::Misc,
         };
 
-        Err(Error::new(Some(raw_code as i64), None, kind))
+        Err(Error::new(Some(raw_code as i64), None, kind).record_native("EGL"))
     }
 }
@@ -0,0 +1,177 @@
+//! Everything related to `EGLStream`.
+
+use std::fmt;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLStreamKHR, EGLint};
+
+use crate::error::{ErrorKind, Result};
+use crate::surface::{PbufferSurface, SurfaceAttributes};
+
+use super::config::Config;
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+use super::surface::Surface;
+
+impl Display {
+    /// Create the [`Stream`] to pass the frames from the producer, like the
+    /// camera, to the GL consumer.
+    ///
+    /// This requires `EGL_KHR_stream`.
+    pub fn create_stream(&self) -> Result<Stream> {
+        if !self.inner.display_extensions.contains("EGL_KHR_stream") {
+            return Err(ErrorKind::NotSupported("EGL streams are not supported").into());
+        }
+
+        let attrs = [egl::NONE as EGLint];
+        let raw = unsafe { self.inner.egl.CreateStreamKHR(*self.inner.raw, attrs.as_ptr()) };
+        if raw == egl::NO_STREAM_KHR {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Stream { display: self.clone(), raw })
+    }
+}
+
+/// The state of the [`Stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamState {
+    /// The stream has neither the producer nor the consumer.
+    Created,
+
+    /// The consumer is connected, but the producer is not yet.
+    Connecting,
+
+    /// The producer is connected, but no frame was inserted yet.
+    Empty,
+
+    /// The frame, which wasn't acquired yet, is available.
+    NewFrameAvailable,
+
+    /// Only the already acquired frame is available.
+    OldFrameAvailable,
+
+    /// The producer or the consumer was destroyed.
+    Disconnected,
+}
+
+/// A wrapper around `EGLStreamKHR`, which is destroyed on drop.
+///
+/// The consumer must be connected before the producer.
+pub struct Stream {
+    display: Display,
+    raw: EGLStreamKHR,
+}
+
+impl Stream {
+    /// Connect the texture bound to `GL_TEXTURE_EXTERNAL_OES` in the
+    /// `context` as the consumer of the stream.
+    ///
+    /// This requires `EGL_KHR_stream_consumer_gltexture`.
+    pub fn consumer_gl_texture_external(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        if !self.display.inner.display_extensions.contains("EGL_KHR_stream_consumer_gltexture") {
+            return Err(
+                ErrorKind::NotSupported("GL texture stream consumer is not supported").into()
+            );
+        }
+
+        context.inner.bind_api();
+        Self::check(unsafe {
+            self.display
+                .inner
+                .egl
+                .StreamConsumerGLTextureExternalKHR(*self.display.inner.raw, self.raw)
+        })
+    }
+
+    /// Latch the most recent frame of the stream into the consumer texture.
+    ///
+    /// The frame stays in the texture until the next acquire, however it
+    /// should be released with [`Self::consumer_release`] once it's no
+    /// longer used, so the producer could reuse it.
+    pub fn consumer_acquire(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        context.inner.bind_api();
+        Self::check(unsafe {
+            self.display.inner.egl.StreamConsumerAcquireKHR(*self.display.inner.raw, self.raw)
+        })
+    }
+
+    /// Release the frame acquired with [`Self::consumer_acquire`] back to the
+    /// producer.
+    pub fn consumer_release(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        context.inner.bind_api();
+        Self::check(unsafe {
+            self.display.inner.egl.StreamConsumerReleaseKHR(*self.display.inner.raw, self.raw)
+        })
+    }
+
+    /// Create the surface acting as the producer of the stream, where each
+    /// [`GlSurface::swap_buffers`] inserts the rendered frame.
+    ///
+    /// This requires `EGL_KHR_stream_producer_eglsurface`.
+    ///
+    /// [`GlSurface::swap_buffers`]: crate::surface::GlSurface::swap_buffers
+    pub fn producer_surface(
+        &self,
+        config: &Config,
+        surface_attributes: &SurfaceAttributes<PbufferSurface>,
+    ) -> Result<Surface<PbufferSurface>> {
+        if !self.display.inner.display_extensions.contains("EGL_KHR_stream_producer_eglsurface") {
+            return Err(ErrorKind::NotSupported("surface stream producer is not supported").into());
+        }
+
+        unsafe { self.display.create_stream_producer_surface(config, self.raw, surface_attributes) }
+    }
+
+    /// The current state of the stream.
+    pub fn state(&self) -> Result<StreamState> {
+        let mut state = 0;
+        Self::check(unsafe {
+            self.display.inner.egl.QueryStreamKHR(
+                *self.display.inner.raw,
+                self.raw,
+                egl::STREAM_STATE_KHR,
+                &mut state,
+            )
+        })?;
+
+        Ok(match state as egl::types::EGLenum {
+            egl::STREAM_STATE_CREATED_KHR => StreamState::Created,
+            egl::STREAM_STATE_CONNECTING_KHR => StreamState::Connecting,
+            egl::STREAM_STATE_EMPTY_KHR => StreamState::Empty,
+            egl::STREAM_STATE_NEW_FRAME_AVAILABLE_KHR => StreamState::NewFrameAvailable,
+            egl::STREAM_STATE_OLD_FRAME_AVAILABLE_KHR => StreamState::OldFrameAvailable,
+            _ => StreamState::Disconnected,
+        })
+    }
+
+    /// The raw `EGLStreamKHR` to pass to the producer outside of glutin.
+    pub fn raw_stream(&self) -> *const std::ffi::c_void {
+        self.raw
+    }
+
+    fn check(result: egl::types::EGLBoolean) -> Result<()> {
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroyStreamKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stream")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
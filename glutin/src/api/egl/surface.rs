@@ -2,10 +2,15 @@
 
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
+#[cfg(free_unix)]
+use std::ptr::NonNull;
+use std::sync::atomic::AtomicBool;
 use std::{ffi, fmt};
 
 use glutin_egl_sys::egl;
-use glutin_egl_sys::egl::types::{EGLAttrib, EGLSurface, EGLint};
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLStreamKHR, EGLSurface, EGLint};
+#[cfg(free_unix)]
+use once_cell::sync::Lazy;
 use raw_window_handle::RawWindowHandle;
 #[cfg(wayland_platform)]
 use wayland_sys::{egl::*, ffi_dispatch};
@@ -18,7 +23,7 @@ use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::{
     AsRawSurface, NativePixmap, PbufferSurface, PixmapSurface, RawSurface, Rect, SurfaceAttributes,
-    SurfaceTypeTrait, SwapInterval, WindowSurface,
+    SurfaceOrigin, SurfaceTypeTrait, SwapInterval, WindowSurface,
 };
 
 use super::config::Config;
@@ -28,12 +33,39 @@ use super::display::Display;
 /// Hint for the attribute list size.
 const ATTR_SIZE_HINT: usize = 8;
 
+#[cfg(free_unix)]
+type GbmSurfaceLockFrontBuffer = unsafe extern "C" fn(*mut ffi::c_void) -> *mut ffi::c_void;
+#[cfg(free_unix)]
+type GbmSurfaceReleaseBuffer = unsafe extern "C" fn(*mut ffi::c_void, *mut ffi::c_void);
+
+/// The `libgbm` functions used to get the buffers for the scanout.
+#[cfg(free_unix)]
+struct Gbm {
+    surface_lock_front_buffer: GbmSurfaceLockFrontBuffer,
+    surface_release_buffer: GbmSurfaceReleaseBuffer,
+    _lib: libloading::Library,
+}
+
+#[cfg(free_unix)]
+static GBM: Lazy<Option<Gbm>> = Lazy::new(|| unsafe {
+    let lib =
+        ["libgbm.so.1", "libgbm.so"].iter().find_map(|path| libloading::Library::new(path).ok())?;
+    let surface_lock_front_buffer =
+        *lib.get::<GbmSurfaceLockFrontBuffer>(b"gbm_surface_lock_front_buffer\0").ok()?;
+    let surface_release_buffer =
+        *lib.get::<GbmSurfaceReleaseBuffer>(b"gbm_surface_release_buffer\0").ok()?;
+    Some(Gbm { surface_lock_front_buffer, surface_release_buffer, _lib: lib })
+});
+
 impl Display {
     pub(crate) unsafe fn create_pbuffer_surface(
         &self,
         config: &Config,
         surface_attributes: &SurfaceAttributes<PbufferSurface>,
     ) -> Result<Surface<PbufferSurface>> {
+        SurfaceAttributes::check_pbuffer_samples(config)?;
+        surface_attributes.check_max_pbuffer_size(config.max_pbuffer_size())?;
+
         let width = surface_attributes.width.unwrap();
         let height = surface_attributes.height.unwrap();
 
@@ -47,6 +79,17 @@ impl Display {
         attrs.push(egl::HEIGHT as EGLint);
         attrs.push(height.get() as EGLint);
 
+        if surface_attributes.largest_pbuffer {
+            attrs.push(egl::LARGEST_PBUFFER as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
+        if surface_attributes.protected {
+            self.inner.check_protected_content()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
         // Push `egl::NONE` to terminate the list.
         attrs.push(egl::NONE as EGLint);
 
@@ -64,6 +107,52 @@ impl Display {
             native_window: None,
             config,
             raw: surface,
+            swap_interval_set: AtomicBool::new(false),
+            _ty: PhantomData,
+        })
+    }
+
+    pub(crate) unsafe fn create_stream_producer_surface(
+        &self,
+        config: &Config,
+        stream: EGLStreamKHR,
+        surface_attributes: &SurfaceAttributes<PbufferSurface>,
+    ) -> Result<Surface<PbufferSurface>> {
+        let width = surface_attributes.width.unwrap();
+        let height = surface_attributes.height.unwrap();
+
+        let mut attrs = vec![
+            egl::WIDTH as EGLint,
+            width.get() as EGLint,
+            egl::HEIGHT as EGLint,
+            height.get() as EGLint,
+        ];
+
+        if surface_attributes.protected {
+            self.inner.check_protected_content()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLint);
+
+        let config = config.clone();
+        let surface = unsafe {
+            Self::check_surface_error(self.inner.egl.CreateStreamProducerSurfaceKHR(
+                *self.inner.raw,
+                *config.inner.raw,
+                stream,
+                attrs.as_ptr(),
+            ))?
+        };
+
+        Ok(Surface {
+            display: self.clone(),
+            native_window: None,
+            config,
+            raw: surface,
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         })
     }
@@ -86,6 +175,12 @@ impl Display {
             attrs.push(colorspace);
         }
 
+        if surface_attributes.protected {
+            self.inner.check_protected_content()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLAttrib);
+            attrs.push(egl::TRUE as EGLAttrib);
+        }
+
         // Push `egl::NONE` to terminate the list.
         attrs.push(egl::NONE as EGLAttrib);
 
@@ -153,6 +248,7 @@ impl Display {
             config,
             native_window: None,
             raw: surface,
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         })
     }
@@ -189,6 +285,12 @@ impl Display {
             attrs.push(colorspace);
         }
 
+        if surface_attributes.protected {
+            self.inner.check_protected_content()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLAttrib);
+            attrs.push(egl::TRUE as EGLAttrib);
+        }
+
         // Push `egl::NONE` to terminate the list.
         attrs.push(egl::NONE as EGLAttrib);
 
@@ -230,6 +332,7 @@ impl Display {
             config,
             native_window: Some(native_window),
             raw: surface,
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         })
     }
@@ -243,12 +346,54 @@ impl Display {
     }
 }
 
+/// The HDR mastering display metadata.
+///
+/// The chromaticities are in the CIE 1931 xy color space and the luminance
+/// values are in cd/m².
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HdrMetadata {
+    /// The red, green, and blue primaries of the mastering display.
+    pub display_primaries: [(f32, f32); 3],
+
+    /// The white point of the mastering display.
+    pub white_point: (f32, f32),
+
+    /// The maximum luminance of the mastering display.
+    pub max_luminance: f32,
+
+    /// The minimum luminance of the mastering display.
+    pub min_luminance: f32,
+
+    /// The maximum content light level (MaxCLL).
+    pub max_content_light_level: Option<f32>,
+
+    /// The maximum frame average light level (MaxFALL).
+    pub max_frame_average_light_level: Option<f32>,
+}
+
+/// The `gbm_bo` locked from the gbm backed [`Surface`].
+///
+/// The buffer must be given back with [`Surface::gbm_release_buffer`].
+#[cfg(free_unix)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct GbmBo(NonNull<ffi::c_void>);
+
+#[cfg(free_unix)]
+impl GbmBo {
+    /// The raw `gbm_bo` pointer, e.g. to get its handle for the KMS
+    /// framebuffer.
+    pub fn as_ptr(&self) -> *mut ffi::c_void {
+        self.0.as_ptr()
+    }
+}
+
 /// A wrapper around `EGLSurface`.
 pub struct Surface<T: SurfaceTypeTrait> {
     display: Display,
     config: Config,
     pub(crate) raw: EGLSurface,
     native_window: Option<NativeWindow>,
+    pub(crate) swap_interval_set: AtomicBool,
     _ty: PhantomData<T>,
 }
 
@@ -256,6 +401,48 @@ pub struct Surface<T: SurfaceTypeTrait> {
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
 impl<T: SurfaceTypeTrait> Surface<T> {
+    /// The `EGLNativeWindowType` the window surface was created with, like
+    /// the `wl_egl_window` on Wayland or the `Window` on X11.
+    ///
+    /// `None` is returned for the pbuffer and pixmap surfaces.
+    ///
+    /// The `wl_egl_window` is owned by the surface, thus it's only valid
+    /// while the surface is alive, while the rest are only valid as long as
+    /// the window the surface was created for.
+    pub fn egl_native_window(&self) -> Option<egl::NativeWindowType> {
+        self.native_window.as_ref().map(NativeWindow::as_native_window)
+    }
+
+    /// Whether the surface is still usable, checked by querying its width
+    /// with `eglQuerySurface`.
+    ///
+    /// The surface becomes invalid when e.g. its native window is destroyed.
+    pub fn is_valid(&self) -> bool {
+        let mut value = 0;
+        unsafe {
+            self.display.inner.egl.QuerySurface(
+                *self.display.inner.raw,
+                self.raw,
+                egl::WIDTH as EGLint,
+                &mut value,
+            ) != egl::FALSE
+        }
+    }
+
+    /// Whether [`GlSurface::resize`] must be called for the surface to follow
+    /// the size of its native window.
+    ///
+    /// This is only the case for the Wayland windows, the size of the rest is
+    /// picked up by the EGL implementation itself.
+    pub fn needs_resize(&self) -> bool {
+        #[cfg(wayland_platform)]
+        if let Some(NativeWindow::Wayland(_)) = self.native_window {
+            return true;
+        }
+
+        false
+    }
+
     /// Swaps the underlying back buffers when the surface is not single
     /// buffered and pass the [`Rect`] information to the system
     /// compositor. Providing empty slice will damage the entire surface.
@@ -304,6 +491,124 @@ impl<T: SurfaceTypeTrait> Surface<T> {
         }
     }
 
+    /// Set the region of the back buffer which is going to be redrawn in
+    /// the current frame, so the driver could skip preserving the rest of
+    /// it. Providing empty slice will mark the entire surface.
+    ///
+    /// This must be called after making the `context` current with the
+    /// surface and querying [`GlSurface::buffer_age`], but before issuing any
+    /// drawing commands for the frame, and only once per frame. The contents
+    /// outside of the damage region are taken from the buffer of the given
+    /// age, so the region must cover everything that has changed since then.
+    /// The frame is then presented with [`Self::swap_buffers_with_damage`]
+    /// or [`GlSurface::swap_buffers`] as usual.
+    ///
+    /// This requires `EGL_KHR_partial_update`.
+    pub fn set_damage_region(
+        &self,
+        context: &PossiblyCurrentContext,
+        rects: &[Rect],
+    ) -> Result<()> {
+        if !self.display.inner.display_extensions.contains("EGL_KHR_partial_update") {
+            return Err(ErrorKind::NotSupported("partial update is not supported").into());
+        }
+
+        context.inner.bind_api();
+
+        let res = unsafe {
+            self.display.inner.egl.SetDamageRegionKHR(
+                *self.display.inner.raw,
+                self.raw,
+                rects.as_ptr() as *mut _,
+                rects.len() as _,
+            )
+        };
+
+        if res == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Attach the HDR mastering display metadata to the surface, so the
+    /// display could tone-map the content correctly.
+    ///
+    /// The metadata takes effect on the next swap. This requires
+    /// `EGL_EXT_surface_SMPTE2086_metadata`, and additionally
+    /// `EGL_EXT_surface_CTA861_3_metadata` when the content light levels are
+    /// provided.
+    pub fn set_hdr_metadata(&self, metadata: &HdrMetadata) -> Result<()> {
+        let extensions = &self.display.inner.display_extensions;
+        if !extensions.contains("EGL_EXT_surface_SMPTE2086_metadata") {
+            return Err(ErrorKind::NotSupported("SMPTE2086 metadata is not supported").into());
+        }
+
+        let has_light_levels = metadata.max_content_light_level.is_some()
+            || metadata.max_frame_average_light_level.is_some();
+        if has_light_levels && !extensions.contains("EGL_EXT_surface_CTA861_3_metadata") {
+            return Err(ErrorKind::NotSupported("CTA861.3 metadata is not supported").into());
+        }
+
+        let [red, green, blue] = metadata.display_primaries;
+        let mut attrs = vec![
+            (egl::SMPTE2086_DISPLAY_PRIMARY_RX_EXT, red.0),
+            (egl::SMPTE2086_DISPLAY_PRIMARY_RY_EXT, red.1),
+            (egl::SMPTE2086_DISPLAY_PRIMARY_GX_EXT, green.0),
+            (egl::SMPTE2086_DISPLAY_PRIMARY_GY_EXT, green.1),
+            (egl::SMPTE2086_DISPLAY_PRIMARY_BX_EXT, blue.0),
+            (egl::SMPTE2086_DISPLAY_PRIMARY_BY_EXT, blue.1),
+            (egl::SMPTE2086_WHITE_POINT_X_EXT, metadata.white_point.0),
+            (egl::SMPTE2086_WHITE_POINT_Y_EXT, metadata.white_point.1),
+            (egl::SMPTE2086_MAX_LUMINANCE_EXT, metadata.max_luminance),
+            (egl::SMPTE2086_MIN_LUMINANCE_EXT, metadata.min_luminance),
+        ];
+
+        if let Some(level) = metadata.max_content_light_level {
+            attrs.push((egl::CTA861_3_MAX_CONTENT_LIGHT_LEVEL_EXT, level));
+        }
+
+        if let Some(level) = metadata.max_frame_average_light_level {
+            attrs.push((egl::CTA861_3_MAX_FRAME_AVERAGE_LEVEL_EXT, level));
+        }
+
+        for (attr, value) in attrs {
+            // The values are passed as fixed point.
+            let value = (value * egl::METADATA_SCALING_EXT as f32).round() as EGLint;
+            let res = unsafe {
+                self.display.inner.egl.SurfaceAttrib(
+                    *self.display.inner.raw,
+                    self.raw,
+                    attr as EGLint,
+                    value,
+                )
+            };
+
+            if res == egl::FALSE {
+                return super::check_error();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The origin of the surface.
+    ///
+    /// The orientation is determined with `EGL_ANGLE_surface_orientation`,
+    /// otherwise [`SurfaceOrigin::BottomLeft`] is assumed.
+    pub fn origin(&self) -> SurfaceOrigin {
+        if !self.display.inner.display_extensions.contains("EGL_ANGLE_surface_orientation") {
+            return SurfaceOrigin::BottomLeft;
+        }
+
+        let orientation = unsafe { self.raw_attribute(egl::SURFACE_ORIENTATION_ANGLE as EGLint) };
+        if orientation & egl::SURFACE_ORIENTATION_INVERT_Y_ANGLE as EGLint != 0 {
+            SurfaceOrigin::TopLeft
+        } else {
+            SurfaceOrigin::BottomLeft
+        }
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -334,12 +639,11 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     type SurfaceType = T;
 
     fn buffer_age(&self) -> u32 {
-        self.display
-            .inner
-            .display_extensions
-            .contains("EGL_EXT_buffer_age")
-            .then(|| unsafe { self.raw_attribute(egl::BUFFER_AGE_EXT as EGLint) })
-            .unwrap_or(0) as u32
+        if self.display.inner.display_extensions.contains("EGL_EXT_buffer_age") {
+            unsafe { self.raw_attribute(egl::BUFFER_AGE_EXT as EGLint) as u32 }
+        } else {
+            0
+        }
     }
 
     fn width(&self) -> Option<u32> {
@@ -406,6 +710,43 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 }
 
+#[cfg(free_unix)]
+impl Surface<WindowSurface> {
+    /// Lock the buffer presented by the last [`GlSurface::swap_buffers`] on
+    /// the gbm backed surface, so it could be scanned out with KMS.
+    ///
+    /// Every locked buffer must be released with [`Self::gbm_release_buffer`]
+    /// once it's no longer displayed, otherwise the surface will run out of
+    /// free buffers to render into.
+    pub fn gbm_lock_front_buffer(&self) -> Result<GbmBo> {
+        let gbm_surface = self.gbm_surface()?;
+        let gbm = GBM.as_ref().ok_or(ErrorKind::NotSupported("libgbm is not available"))?;
+        let bo = unsafe { (gbm.surface_lock_front_buffer)(gbm_surface) };
+        NonNull::new(bo).map(GbmBo).ok_or_else(|| ErrorKind::BadAccess.into())
+    }
+
+    /// Release the buffer locked with [`Self::gbm_lock_front_buffer`] back to
+    /// the gbm backed surface.
+    ///
+    /// # Safety
+    ///
+    /// The `bo` must be locked from this surface and it must not be used by
+    /// the KMS anymore, e.g. after the page flip to the next buffer is done.
+    pub unsafe fn gbm_release_buffer(&self, bo: GbmBo) -> Result<()> {
+        let gbm_surface = self.gbm_surface()?;
+        let gbm = GBM.as_ref().ok_or(ErrorKind::NotSupported("libgbm is not available"))?;
+        unsafe { (gbm.surface_release_buffer)(gbm_surface, bo.as_ptr()) };
+        Ok(())
+    }
+
+    fn gbm_surface(&self) -> Result<*mut ffi::c_void> {
+        match &self.native_window {
+            Some(NativeWindow::Gbm(gbm_surface)) => Ok(*gbm_surface),
+            _ => Err(ErrorKind::NotSupported("surface is not backed by gbm").into()),
+        }
+    }
+}
+
 impl<T: SurfaceTypeTrait> GetGlConfig for Surface<T> {
     type Target = Config;
 
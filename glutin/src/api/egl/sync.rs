@@ -0,0 +1,144 @@
+//! Everything related to `EGLSync`.
+
+use std::fmt;
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::time::Duration;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLSyncKHR, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::display::Display;
+
+impl Display {
+    /// Make the current context wait for the native fence, e.g. the
+    /// `SYNC_FD` exported from the Vulkan semaphore, before executing the
+    /// subsequently submitted GL commands.
+    ///
+    /// The wait happens on the GPU, so the function returns right away. The
+    /// `fence` is closed once it's signaled.
+    ///
+    /// This requires `EGL_ANDROID_native_fence_sync` and `EGL_KHR_wait_sync`.
+    #[cfg(unix)]
+    pub fn wait_native_fence(&self, fence: OwnedFd) -> Result<()> {
+        let extensions = &self.inner.display_extensions;
+        if !extensions.contains("EGL_ANDROID_native_fence_sync")
+            || !extensions.contains("EGL_KHR_wait_sync")
+        {
+            return Err(ErrorKind::NotSupported("native fence sync is not supported").into());
+        }
+
+        let attrs =
+            [egl::SYNC_NATIVE_FENCE_FD_ANDROID as EGLint, fence.as_raw_fd(), egl::NONE as EGLint];
+
+        unsafe {
+            let sync = self.inner.egl.CreateSyncKHR(
+                *self.inner.raw,
+                egl::SYNC_NATIVE_FENCE_ANDROID,
+                attrs.as_ptr(),
+            );
+            if sync.is_null() {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            // The fence is owned by the sync object now.
+            std::mem::forget(fence);
+
+            let result =
+                if self.inner.egl.WaitSyncKHR(*self.inner.raw, sync, 0) == egl::FALSE as EGLint {
+                    super::check_error()
+                } else {
+                    Ok(())
+                };
+
+            // The sync object is kept alive by the driver until it's signaled.
+            self.inner.egl.DestroySyncKHR(*self.inner.raw, sync);
+
+            result
+        }
+    }
+
+    /// Insert the fence into the command stream of the context current on
+    /// the calling thread, which is signaled once all the preceding commands
+    /// are complete.
+    ///
+    /// This requires `EGL_KHR_fence_sync`.
+    pub fn create_fence(&self) -> Result<Fence> {
+        if !self.inner.display_extensions.contains("EGL_KHR_fence_sync") {
+            return Err(ErrorKind::NotSupported("fence sync is not supported").into());
+        }
+
+        let raw = unsafe {
+            self.inner.egl.CreateSyncKHR(*self.inner.raw, egl::SYNC_FENCE_KHR, std::ptr::null())
+        };
+        if raw.is_null() {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Fence { display: self.clone(), raw })
+    }
+}
+
+/// A wrapper around the fence `EGLSync`, which is destroyed on drop.
+pub struct Fence {
+    display: Display,
+    raw: EGLSyncKHR,
+}
+
+// SAFETY: The sync objects belong to the display and could be waited on from
+// any thread.
+unsafe impl Send for Fence {}
+unsafe impl Sync for Fence {}
+
+impl Fence {
+    /// Block until the fence is signaled or the `timeout` expires, flushing
+    /// the commands of the context which inserted it.
+    ///
+    /// Returns `true` when the fence was signaled and `false` on timeout.
+    /// Passing `None` waits without the timeout.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<bool> {
+        let timeout = match timeout {
+            Some(timeout) => u64::try_from(timeout.as_nanos()).unwrap_or(egl::FOREVER),
+            None => egl::FOREVER,
+        };
+
+        let result = unsafe {
+            self.display.inner.egl.ClientWaitSyncKHR(
+                *self.display.inner.raw,
+                self.raw,
+                egl::SYNC_FLUSH_COMMANDS_BIT as EGLint,
+                timeout,
+            )
+        };
+
+        match result as egl::types::EGLenum {
+            egl::CONDITION_SATISFIED => Ok(true),
+            egl::TIMEOUT_EXPIRED => Ok(false),
+            _ => super::check_error().map(|_| false),
+        }
+    }
+
+    /// The raw `EGLSync` of the fence.
+    pub fn raw_sync(&self) -> EGLSyncKHR {
+        self.raw
+    }
+}
+
+impl Drop for Fence {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroySyncKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for Fence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fence")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
@@ -1,20 +1,22 @@
 //! Everything related to finding and manipulating the `GLXFBConfig`.
 
-use std::ops::Deref;
+use std::ops::{Deref, RangeInclusive};
 use std::os::raw::c_int;
 use std::sync::Arc;
 use std::{fmt, slice};
 
 use glutin_glx_sys::glx::types::GLXFBConfig;
 use glutin_glx_sys::{glx, glx_extra};
+use once_cell::sync::OnceCell;
 use raw_window_handle::RawWindowHandle;
 
 use crate::config::{
     Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, RawConfig,
+    Transparency,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
-use crate::platform::x11::{X11GlConfigExt, X11VisualInfo, XLIB};
+use crate::platform::x11::{X11GlConfigExt, X11VisualInfo, X11VisualType, XLIB};
 use crate::private::Sealed;
 
 use super::display::Display;
@@ -30,8 +32,12 @@ impl Display {
         match template.color_buffer_type {
             ColorBufferType::Rgb { r_size, g_size, b_size } => {
                 // Type.
+                let visual_type = match template.x11_visual_type {
+                    X11VisualType::TrueColor => glx::TRUE_COLOR,
+                    X11VisualType::DirectColor => glx::DIRECT_COLOR,
+                };
                 config_attributes.push(glx::X_VISUAL_TYPE as c_int);
-                config_attributes.push(glx::TRUE_COLOR as c_int);
+                config_attributes.push(visual_type as c_int);
 
                 // R.
                 config_attributes.push(glx::RED_SIZE as c_int);
@@ -145,6 +151,19 @@ impl Display {
             }
         }
 
+        // Add sRGB.
+        if template.srgb_capable {
+            let extensions = &self.inner.client_extensions;
+            if extensions.contains("GLX_ARB_framebuffer_sRGB") {
+                config_attributes.push(glx_extra::FRAMEBUFFER_SRGB_CAPABLE_ARB as c_int);
+            } else if extensions.contains("GLX_EXT_framebuffer_sRGB") {
+                config_attributes.push(glx_extra::FRAMEBUFFER_SRGB_CAPABLE_EXT as c_int);
+            } else {
+                return Err(ErrorKind::NotSupported("sRGB framebuffers are not supported").into());
+            }
+            config_attributes.push(1);
+        }
+
         // Push X11 `None` to terminate the list.
         config_attributes.push(0);
 
@@ -158,7 +177,7 @@ impl Display {
             );
 
             if raw_configs.is_null() {
-                return Err(ErrorKind::BadConfig.into());
+                return Err(ErrorKind::NoMatchingConfig.into());
             }
 
             let configs = slice::from_raw_parts_mut(raw_configs, num_configs as usize).to_vec();
@@ -170,12 +189,13 @@ impl Display {
                 .into_iter()
                 .map(move |raw| {
                     let raw = GlxConfig(raw);
-                    let inner = Arc::new(ConfigInner { display: self.clone(), raw });
+                    let inner = Arc::new(ConfigInner::new(self.clone(), raw));
                     Config { inner }
                 })
                 .filter(move |config| {
                     !template.transparency || config.supports_transparency().unwrap_or(false)
-                });
+                })
+                .filter(move |config| template.fits_pbuffer_sizes(|| config.max_pbuffer_size()));
 
             Ok(Box::new(iter))
         }
@@ -189,10 +209,25 @@ pub struct Config {
 }
 
 impl Config {
+    /// The attributes from [`CACHED_ATTRIBUTES`] are queried on the first
+    /// access and served from the cache afterwards.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
     unsafe fn raw_attribute(&self, attr: c_int) -> c_int {
+        match CACHED_ATTRIBUTES.binary_search(&(attr as _)) {
+            Ok(index) => {
+                *self.inner.attributes[index].get_or_init(|| unsafe { self.query_attribute(attr) })
+            },
+            Err(_) => unsafe { self.query_attribute(attr) },
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that the attribute could be present.
+    unsafe fn query_attribute(&self, attr: c_int) -> c_int {
         unsafe {
             let mut val = 0;
             self.inner.display.inner.glx.GetFBConfigAttrib(
@@ -205,6 +240,11 @@ impl Config {
         }
     }
 
+    /// The `GLX_FBCONFIG_ID` of the config.
+    pub(crate) fn config_id(&self) -> u32 {
+        unsafe { self.raw_attribute(glx::FBCONFIG_ID as c_int) as u32 }
+    }
+
     pub(crate) fn is_single_buffered(&self) -> bool {
         unsafe { self.raw_attribute(glx::DOUBLEBUFFER as c_int) == 0 }
     }
@@ -214,7 +254,7 @@ impl GlConfig for Config {
     fn color_buffer_type(&self) -> Option<ColorBufferType> {
         unsafe {
             match self.raw_attribute(glx::X_VISUAL_TYPE as c_int) as _ {
-                glx::TRUE_COLOR => {
+                glx::TRUE_COLOR | glx::DIRECT_COLOR => {
                     let r_size = self.raw_attribute(glx::RED_SIZE as c_int) as u8;
                     let g_size = self.raw_attribute(glx::GREEN_SIZE as c_int) as u8;
                     let b_size = self.raw_attribute(glx::BLUE_SIZE as c_int) as u8;
@@ -290,6 +330,26 @@ impl GlConfig for Config {
         self.x11_visual().map(|visual| visual.supports_transparency())
     }
 
+    fn transparency(&self) -> Option<Transparency> {
+        unsafe {
+            if self.raw_attribute(glx::TRANSPARENT_TYPE as c_int) == glx::TRANSPARENT_RGB as c_int {
+                return Some(Transparency::ChromaKey {
+                    r: self.raw_attribute(glx::TRANSPARENT_RED_VALUE as c_int) as u32,
+                    g: self.raw_attribute(glx::TRANSPARENT_GREEN_VALUE as c_int) as u32,
+                    b: self.raw_attribute(glx::TRANSPARENT_BLUE_VALUE as c_int) as u32,
+                });
+            }
+        }
+
+        self.supports_transparency().map(|alpha| {
+            if alpha {
+                Transparency::AlphaBlend
+            } else {
+                Transparency::Opaque
+            }
+        })
+    }
+
     fn api(&self) -> Api {
         let mut api = Api::OPENGL;
         if self.inner.display.inner.features.contains(DisplayFeatures::CREATE_ES_CONTEXT) {
@@ -298,6 +358,32 @@ impl GlConfig for Config {
 
         api
     }
+
+    fn conformant_apis(&self) -> Option<Api> {
+        let caveat = unsafe { self.raw_attribute(glx::CONFIG_CAVEAT as c_int) };
+        if caveat == glx::NON_CONFORMANT_CONFIG as c_int {
+            Some(Api::empty())
+        } else {
+            Some(self.api())
+        }
+    }
+
+    fn max_pbuffer_size(&self) -> Option<(u32, u32, u32)> {
+        unsafe {
+            let width = self.raw_attribute(glx::MAX_PBUFFER_WIDTH as c_int) as u32;
+            let height = self.raw_attribute(glx::MAX_PBUFFER_HEIGHT as c_int) as u32;
+            let pixels = self.raw_attribute(glx::MAX_PBUFFER_PIXELS as c_int) as u32;
+            Some((width, height, pixels))
+        }
+    }
+
+    fn native_renderable(&self) -> Option<bool> {
+        None
+    }
+
+    fn swap_interval_range(&self) -> Option<RangeInclusive<u32>> {
+        None
+    }
 }
 
 impl X11GlConfigExt for Config {
@@ -337,9 +423,52 @@ impl AsRawConfig for Config {
 
 impl Sealed for Config {}
 
+/// The attributes backing the [`GlConfig`] accessors, which are cached, since
+/// the config pickers usually inspect them across all the configs.
+///
+/// The attributes are sorted to look them up with the binary search.
+const CACHED_ATTRIBUTES: [glx::types::GLenum; 20] = [
+    glx::DOUBLEBUFFER,
+    glx::RED_SIZE,
+    glx::GREEN_SIZE,
+    glx::BLUE_SIZE,
+    glx::ALPHA_SIZE,
+    glx::DEPTH_SIZE,
+    glx::STENCIL_SIZE,
+    glx::CONFIG_CAVEAT,
+    glx::X_VISUAL_TYPE,
+    glx::TRANSPARENT_TYPE,
+    glx::TRANSPARENT_RED_VALUE,
+    glx::TRANSPARENT_GREEN_VALUE,
+    glx::TRANSPARENT_BLUE_VALUE,
+    glx::DRAWABLE_TYPE,
+    glx::RENDER_TYPE,
+    glx::FBCONFIG_ID,
+    glx::MAX_PBUFFER_WIDTH,
+    glx::MAX_PBUFFER_HEIGHT,
+    glx::MAX_PBUFFER_PIXELS,
+    glx::SAMPLES,
+];
+
+// Fail the build when the attributes aren't sorted.
+const _: () = {
+    let mut index = 1;
+    while index < CACHED_ATTRIBUTES.len() {
+        assert!(CACHED_ATTRIBUTES[index - 1] < CACHED_ATTRIBUTES[index]);
+        index += 1;
+    }
+};
+
 pub(crate) struct ConfigInner {
     display: Display,
     pub(crate) raw: GlxConfig,
+    attributes: [OnceCell<c_int>; CACHED_ATTRIBUTES.len()],
+}
+
+impl ConfigInner {
+    fn new(display: Display, raw: GlxConfig) -> Self {
+        Self { display, raw, attributes: Default::default() }
+    }
 }
 
 impl PartialEq for ConfigInner {
@@ -5,16 +5,16 @@ use std::marker::PhantomData;
 use std::ops::Deref;
 use std::os::raw::c_int;
 
-use glutin_glx_sys::glx::types::GLXContext;
+use glutin_glx_sys::glx::types::{GLXContext, GLXDrawable};
 use glutin_glx_sys::{glx, glx_extra};
 
 use crate::config::GetGlConfig;
 use crate::context::{
-    self, AsRawContext, ContextApi, ContextAttributes, GlProfile, Priority, RawContext,
-    ReleaseBehavior, Robustness, Version,
+    self, AsRawContext, ContextApi, ContextAttributes, CreationAttributes, DebugCallbackSlot,
+    GlProfile, Priority, RawContext, ReleaseBehavior, Robustness, Version,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
-use crate::error::{ErrorKind, Result};
+use crate::error::{Error, ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::SurfaceTypeTrait;
@@ -29,6 +29,20 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        context_attributes.with_robustness_fallback(|context_attributes| unsafe {
+            self.create_context_with_attributes(config, context_attributes)
+        })
+    }
+
+    unsafe fn create_context_with_attributes(
+        &self,
+        config: &Config,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext> {
+        if context_attributes.protected {
+            return Err(ErrorKind::NotSupported("protected content is not supported").into());
+        }
+
         let shared_context = if let Some(shared_context) =
             context_attributes.shared_context.as_ref()
         {
@@ -41,18 +55,32 @@ impl Display {
             std::ptr::null()
         };
 
-        let (context, supports_surfaceless) =
-            if self.inner.client_extensions.contains("GLX_ARB_create_context")
-                && self.inner.glx_extra.is_some()
-            {
-                self.create_context_arb(config, context_attributes, shared_context)?
-            } else {
-                (self.create_context_legacy(config, shared_context)?, false)
-            };
+        if context_attributes.priority.is_some() {
+            context_attributes.ignore_attribute("context priority is not supported")?;
+        }
 
-        // Failed to create the context.
+        let legacy_ignored = context_attributes.api.is_some()
+            || context_attributes.profile.is_some()
+            || context_attributes.debug;
+        let supports_arb = self.inner.client_extensions.contains("GLX_ARB_create_context")
+            && self.inner.glx_extra.is_some();
+        if legacy_ignored && !supports_arb {
+            context_attributes.ignore_attribute("GLX_ARB_create_context is not supported")?;
+        }
+
+        let (context, supports_surfaceless) = if supports_arb {
+            self.create_context_arb(config, context_attributes, shared_context)?
+        } else {
+            (self.create_context_legacy(config, shared_context)?, false)
+        };
+
+        // Failed to create the context without X11 reporting the error.
         if context.is_null() {
-            return Err(ErrorKind::BadContext.into());
+            return Err(Error::new(
+                None,
+                Some(String::from("context creation failed without X11 error")),
+                ErrorKind::BadContext,
+            ));
         }
 
         let config = config.clone();
@@ -63,6 +91,14 @@ impl Display {
             raw: GlxContext(context),
             is_gles,
             supports_surfaceless,
+            // The legacy context creation can't request the robustness.
+            robustness: if supports_arb {
+                context_attributes.robustness
+            } else {
+                Robustness::NotRobust
+            },
+            attributes: CreationAttributes::new(context_attributes),
+            debug_callback: Default::default(),
         };
 
         Ok(NotCurrentContext::new(inner))
@@ -137,12 +173,13 @@ impl Display {
         if self.inner.features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS) {
             match context_attributes.robustness {
                 Robustness::NotRobust => (),
-                Robustness::RobustNoResetNotification => {
+                Robustness::RobustNoResetNotification
+                | Robustness::TryRobustNoResetNotification => {
                     attrs.push(glx_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
                     attrs.push(glx_extra::NO_RESET_NOTIFICATION_ARB as c_int);
                     flags |= glx_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
                 },
-                Robustness::RobustLoseContextOnReset => {
+                Robustness::RobustLoseContextOnReset | Robustness::TryRobustLoseContextOnReset => {
                     attrs.push(glx_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
                     attrs.push(glx_extra::LOSE_CONTEXT_ON_RESET_ARB as c_int);
                     flags |= glx_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
@@ -170,6 +207,8 @@ impl Display {
         // Debug flag.
         if context_attributes.debug && !requested_no_error {
             flags |= glx_extra::CONTEXT_DEBUG_BIT_ARB as c_int;
+        } else if context_attributes.debug {
+            context_attributes.ignore_attribute("debug context is not supported")?;
         }
 
         if flags != 0 {
@@ -285,6 +324,10 @@ impl GlContext for NotCurrentContext {
     fn priority(&self) -> Priority {
         Priority::Medium
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl GetGlConfig for NotCurrentContext {
@@ -361,6 +404,10 @@ impl GlContext for PossiblyCurrentContext {
     fn priority(&self) -> Priority {
         Priority::Medium
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl GetGlConfig for PossiblyCurrentContext {
@@ -387,12 +434,81 @@ impl AsRawContext for PossiblyCurrentContext {
 
 impl Sealed for PossiblyCurrentContext {}
 
+impl PossiblyCurrentContext {
+    pub(crate) fn context_attributes(&self) -> &ContextAttributes {
+        &self.inner.attributes
+    }
+
+    pub(crate) fn debug_callback(&self) -> &DebugCallbackSlot {
+        &self.inner.debug_callback
+    }
+
+    /// Whether the context is still usable, checked by querying its config
+    /// with `glXQueryContext`.
+    pub fn is_valid(&self) -> bool {
+        let glx = &self.inner.display.inner.glx;
+        let mut value = 0;
+        super::last_glx_error(|| unsafe {
+            glx.QueryContext(
+                self.inner.display.inner.raw.cast(),
+                *self.inner.raw,
+                glx::FBCONFIG_ID as c_int,
+                &mut value,
+            )
+        })
+        .is_ok_and(|status| status == 0)
+    }
+
+    /// Save the context current on the thread.
+    pub(crate) fn save_current(&self) -> CurrentState {
+        let glx = &self.inner.display.inner.glx;
+        unsafe {
+            CurrentState {
+                display: glx.GetCurrentDisplay(),
+                context: glx.GetCurrentContext(),
+                draw: glx.GetCurrentDrawable(),
+                read: glx.GetCurrentReadDrawable(),
+            }
+        }
+    }
+
+    /// Restore the state saved with [`Self::save_current`].
+    pub(crate) fn restore_current(&self, state: &CurrentState) -> Result<()> {
+        if state.context.is_null() {
+            return self.inner.make_not_current();
+        }
+
+        super::last_glx_error(|| unsafe {
+            self.inner.display.inner.glx.MakeContextCurrent(
+                state.display,
+                state.draw,
+                state.read,
+                state.context,
+            );
+        })
+    }
+}
+
+/// The current context of the thread saved by the [`CurrentContextGuard`].
+///
+/// [`CurrentContextGuard`]: crate::context::CurrentContextGuard
+#[derive(Debug)]
+pub(crate) struct CurrentState {
+    display: *mut glx::types::Display,
+    context: GLXContext,
+    draw: GLXDrawable,
+    read: GLXDrawable,
+}
+
 struct ContextInner {
     display: Display,
     config: Config,
     raw: GlxContext,
     is_gles: bool,
     supports_surfaceless: bool,
+    robustness: Robustness,
+    attributes: CreationAttributes,
+    debug_callback: DebugCallbackSlot,
 }
 
 impl ContextInner {
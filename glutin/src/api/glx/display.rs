@@ -41,6 +41,19 @@ impl Display {
     pub unsafe fn new(
         display: RawDisplayHandle,
         error_hook_registrar: XlibErrorHookRegistrar,
+    ) -> Result<Self> {
+        unsafe { Self::with_options(display, error_hook_registrar, GlxOptions::default()) }
+    }
+
+    /// Create GLX display with the given [`GlxOptions`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn with_options(
+        display: RawDisplayHandle,
+        error_hook_registrar: XlibErrorHookRegistrar,
+        options: GlxOptions,
     ) -> Result<Self> {
         // Don't load GLX when unsupported platform was requested.
         let (display, screen) = match display {
@@ -79,12 +92,16 @@ impl Display {
         //
         // The easiest way to do this is to just call `glXQueryVersion()` before
         // doing anything else. See: https://www.virtualbox.org/ticket/8293
-        let version = unsafe {
-            let (mut major, mut minor) = (0, 0);
-            if glx.QueryVersion(display.0, &mut major, &mut minor) == 0 {
-                return Err(ErrorKind::InitializationFailed.into());
+        let version = if options.virtualbox_workaround {
+            unsafe {
+                let (mut major, mut minor) = (0, 0);
+                if glx.QueryVersion(display.0, &mut major, &mut minor) == 0 {
+                    return Err(ErrorKind::InitializationFailed.into());
+                }
+                Version::new(major as u8, minor as u8)
             }
-            Version::new(major as u8, minor as u8)
+        } else {
+            get_server_version(glx, display, screen).ok_or(ErrorKind::InitializationFailed)?
         };
 
         if version < Version::new(1, 3) {
@@ -119,7 +136,8 @@ impl Display {
         extensions: &HashSet<&'static str>,
         version: Version,
     ) -> DisplayFeatures {
-        let mut features = DisplayFeatures::empty();
+        // `glXMakeContextCurrent` is a part of GLX 1.3.
+        let mut features = DisplayFeatures::SEPARATE_READ_SURFACE;
 
         features.set(
             DisplayFeatures::MULTISAMPLING_PIXEL_FORMATS,
@@ -279,6 +297,66 @@ impl Deref for GlxDisplay {
     }
 }
 
+/// The options of the GLX [`Display`] creation.
+///
+/// Those are passed to [`Display::with_options`] or with the
+/// [`DisplayApiPreference::GlxWithOptions`].
+///
+/// [`DisplayApiPreference::GlxWithOptions`]: crate::display::DisplayApiPreference::GlxWithOptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlxOptions {
+    virtualbox_workaround: bool,
+}
+
+impl Default for GlxOptions {
+    fn default() -> Self {
+        Self { virtualbox_workaround: true }
+    }
+}
+
+impl GlxOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `glXQueryVersion` before anything else for the VirtualBox's
+    /// OpenGL driver.
+    ///
+    /// That driver applies binary patches to Mesa once it's loaded, thus it
+    /// requires a call handled by it to happen before anything else, see
+    /// <https://www.virtualbox.org/ticket/8293>. The `glXQueryVersion` is used
+    /// for that, which is the server round trip, and its result is the GLX
+    /// version of the display.
+    ///
+    /// When disabled, the GLX version is taken from the server's `GLX_VERSION`
+    /// string instead, which is fetched by the GLX library along with the
+    /// connection setup. Only disable it when the application is known to not
+    /// run on VirtualBox.
+    ///
+    /// The default is `true`.
+    pub fn with_virtualbox_workaround(mut self, workaround: bool) -> Self {
+        self.virtualbox_workaround = workaround;
+        self
+    }
+}
+
+/// Parse the GLX version of the server, which looks like `1.4`.
+fn get_server_version(glx: &Glx, display: GlxDisplay, screen: i32) -> Option<Version> {
+    let version = unsafe {
+        let version = glx.QueryServerString(display.0, screen, glx::VERSION as i32);
+        if version.is_null() {
+            return None;
+        }
+
+        CStr::from_ptr(version).to_str().ok()?
+    };
+
+    let version = version.split(' ').next()?;
+    let (major, minor) = version.split_once('.')?;
+    Some(Version::new(major.parse().ok()?, minor.parse().ok()?))
+}
+
 /// Load the GLX extensions.
 fn get_extensions(glx: &Glx, display: GlxDisplay) -> HashSet<&'static str> {
     unsafe {
@@ -47,7 +47,7 @@ static LAST_GLX_ERROR: Lazy<Mutex<Option<Error>>> = Lazy::new(|| Mutex::new(None
 /// the winit's error.
 static SYNCING_GLX_ERROR: AtomicBool = AtomicBool::new(false);
 
-static GLX: Lazy<Option<Glx>> = Lazy::new(|| {
+pub(crate) static GLX: Lazy<Option<Glx>> = Lazy::new(|| {
     let paths = ["libGL.so.1", "libGL.so"];
 
     unsafe { SymWrapper::new(&paths).map(Glx).ok() }
@@ -132,27 +132,34 @@ fn glx_error_hook(_display: *mut ffi::c_void, xerror_event: *mut ffi::c_void) ->
         let glx_code = code as i32 - GLX_BASE_ERROR.load(Ordering::Relaxed);
 
         // Get the kind of the error.
-        let kind = match code as u8 {
-            xlib::BadValue => ErrorKind::BadAttribute,
-            xlib::BadMatch => ErrorKind::BadMatch,
-            xlib::BadWindow => ErrorKind::BadNativeWindow,
-            xlib::BadAlloc => ErrorKind::OutOfMemory,
-            xlib::BadPixmap => ErrorKind::BadPixmap,
-            xlib::BadAccess => ErrorKind::BadAccess,
+        let (kind, glx_name) = match code as u8 {
+            xlib::BadValue => (ErrorKind::BadAttribute, None),
+            xlib::BadMatch => (ErrorKind::BadMatch, None),
+            xlib::BadWindow => (ErrorKind::BadNativeWindow, None),
+            xlib::BadDrawable => (ErrorKind::BadSurface, None),
+            xlib::BadAlloc => (ErrorKind::OutOfMemory, None),
+            xlib::BadPixmap => (ErrorKind::BadPixmap, None),
+            xlib::BadAccess => (ErrorKind::BadAccess, None),
             _ if glx_code >= 0 => match glx_code as glx::types::GLenum {
-                glx::PROTO_BAD_CONTEXT => ErrorKind::BadContext,
-                glx::PROTO_BAD_CONTEXT_STATE => ErrorKind::BadContext,
-                glx::PROTO_BAD_CURRENT_DRAWABLE => ErrorKind::BadCurrentSurface,
-                glx::PROTO_BAD_CURRENT_WINDOW => ErrorKind::BadCurrentSurface,
-                glx::PROTO_BAD_FBCONFIG => ErrorKind::BadConfig,
-                glx::PROTO_BAD_PBUFFER => ErrorKind::BadPbuffer,
-                glx::PROTO_BAD_PIXMAP => ErrorKind::BadPixmap,
-                glx::PROTO_UNSUPPORTED_PRIVATE_REQUEST => ErrorKind::Misc,
-                glx::PROTO_BAD_DRAWABLE => ErrorKind::BadSurface,
-                glx::PROTO_BAD_WINDOW => ErrorKind::BadSurface,
-                glx::PROTO_BAD_CONTEXT_TAG => ErrorKind::Misc,
-                glx::PROTO_BAD_RENDER_REQUEST => ErrorKind::Misc,
-                glx::PROTO_BAD_LARGE_REQUEST => ErrorKind::Misc,
+                glx::PROTO_BAD_CONTEXT => (ErrorKind::BadContext, Some("GLXBadContext")),
+                glx::PROTO_BAD_CONTEXT_STATE => (ErrorKind::BadContext, Some("GLXBadContextState")),
+                glx::PROTO_BAD_CURRENT_DRAWABLE => {
+                    (ErrorKind::BadCurrentSurface, Some("GLXBadCurrentDrawable"))
+                },
+                glx::PROTO_BAD_CURRENT_WINDOW => {
+                    (ErrorKind::BadCurrentSurface, Some("GLXBadCurrentWindow"))
+                },
+                glx::PROTO_BAD_FBCONFIG => (ErrorKind::BadConfig, Some("GLXBadFBConfig")),
+                glx::PROTO_BAD_PBUFFER => (ErrorKind::BadPbuffer, Some("GLXBadPbuffer")),
+                glx::PROTO_BAD_PIXMAP => (ErrorKind::BadPixmap, Some("GLXBadPixmap")),
+                glx::PROTO_UNSUPPORTED_PRIVATE_REQUEST => {
+                    (ErrorKind::Misc, Some("GLXUnsupportedPrivateRequest"))
+                },
+                glx::PROTO_BAD_DRAWABLE => (ErrorKind::BadSurface, Some("GLXBadDrawable")),
+                glx::PROTO_BAD_WINDOW => (ErrorKind::BadSurface, Some("GLXBadWindow")),
+                glx::PROTO_BAD_CONTEXT_TAG => (ErrorKind::Misc, Some("GLXBadContextTag")),
+                glx::PROTO_BAD_RENDER_REQUEST => (ErrorKind::Misc, Some("GLXBadRenderRequest")),
+                glx::PROTO_BAD_LARGE_REQUEST => (ErrorKind::Misc, Some("GLXBadLargeRequest")),
                 _ => return false,
             },
             _ => return false,
@@ -166,10 +173,18 @@ fn glx_error_hook(_display: *mut ffi::c_void, xerror_event: *mut ffi::c_void) ->
             buf.as_mut_ptr() as *mut _,
             buf.len() as _,
         );
-        let description = CStr::from_ptr(buf.as_ptr() as *const _).to_string_lossy().to_string();
+        let text = CStr::from_ptr(buf.as_ptr() as *const _).to_string_lossy();
+
+        // Xlib doesn't always know the names of the GLX errors, so prefer ours.
+        let description = format!(
+            "{} (request code {}, minor code {})",
+            glx_name.unwrap_or(&text),
+            (*xerror).request_code,
+            (*xerror).minor_code,
+        );
 
         *LAST_GLX_ERROR.lock().unwrap() =
-            Some(Error::new(Some(code as _), Some(description), kind));
+            Some(Error::new(Some(code as _), Some(description), kind).record_native("GLX"));
 
         true
     }
@@ -4,12 +4,13 @@ use std::fmt;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
 use std::os::raw::{c_int, c_uint};
+use std::sync::atomic::AtomicBool;
 
 use glutin_glx_sys::glx::types::GLXWindow;
 use glutin_glx_sys::{glx, glx_extra};
 use raw_window_handle::RawWindowHandle;
 
-use crate::config::GetGlConfig;
+use crate::config::{GetGlConfig, GlConfig};
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::private::Sealed;
@@ -21,6 +22,7 @@ use crate::surface::{
 use super::config::Config;
 use super::context::PossiblyCurrentContext;
 use super::display::Display;
+use super::GlxExtra;
 
 /// Hint for the attributes array.
 const ATTR_SIZE_HINT: usize = 8;
@@ -31,6 +33,8 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<PixmapSurface>,
     ) -> Result<Surface<PixmapSurface>> {
+        surface_attributes.check_protected_unsupported()?;
+
         let native_pixmap = surface_attributes.native_pixmap.as_ref().unwrap();
         let xid = match native_pixmap {
             NativePixmap::XlibPixmap(xid) => {
@@ -67,6 +71,7 @@ impl Display {
             config,
             raw: surface,
             _nosendsync: PhantomData,
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         })
     }
@@ -76,6 +81,10 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<PbufferSurface>,
     ) -> Result<Surface<PbufferSurface>> {
+        surface_attributes.check_protected_unsupported()?;
+        SurfaceAttributes::check_pbuffer_samples(config)?;
+        surface_attributes.check_max_pbuffer_size(config.max_pbuffer_size())?;
+
         let width = surface_attributes.width.unwrap();
         let height = surface_attributes.height.unwrap();
 
@@ -101,6 +110,7 @@ impl Display {
             config,
             raw: surface,
             _nosendsync: PhantomData,
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         })
     }
@@ -110,6 +120,8 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<WindowSurface>,
     ) -> Result<Surface<WindowSurface>> {
+        surface_attributes.check_protected_unsupported()?;
+
         let window = match surface_attributes.raw_window_handle.unwrap() {
             RawWindowHandle::Xlib(window_handle) => {
                 if window_handle.window == 0 {
@@ -145,6 +157,7 @@ impl Display {
             config,
             raw: surface,
             _nosendsync: PhantomData,
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         })
     }
@@ -156,6 +169,7 @@ pub struct Surface<T: SurfaceTypeTrait> {
     config: Config,
     pub(crate) raw: GLXWindow,
     _nosendsync: PhantomData<*const std::ffi::c_void>,
+    pub(crate) swap_interval_set: AtomicBool,
     _ty: PhantomData<T>,
 }
 
@@ -163,6 +177,23 @@ pub struct Surface<T: SurfaceTypeTrait> {
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
 impl<T: SurfaceTypeTrait> Surface<T> {
+    /// Whether the surface is still usable, checked by querying its width
+    /// with `glXQueryDrawable`.
+    ///
+    /// The surface becomes invalid when e.g. its X11 window is destroyed.
+    pub fn is_valid(&self) -> bool {
+        let mut value = 0;
+        super::last_glx_error(|| unsafe {
+            self.display.inner.glx.QueryDrawable(
+                self.display.inner.raw.cast(),
+                self.raw,
+                glx::WIDTH as c_int,
+                &mut value,
+            )
+        })
+        .is_ok()
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -182,6 +213,80 @@ impl<T: SurfaceTypeTrait> Surface<T> {
     }
 }
 
+impl Surface<WindowSurface> {
+    /// The refresh rate of the output the surface is presented on in mHz, as
+    /// reported by `glXGetMscRateOML`.
+    ///
+    /// This requires `GLX_OML_sync_control`.
+    pub fn refresh_rate_millihz(&self) -> Option<u32> {
+        let extra =
+            self.display.inner.glx_extra.filter(|_| {
+                self.display.inner.client_extensions.contains("GLX_OML_sync_control")
+            })?;
+
+        let (mut numerator, mut denominator) = (0, 0);
+        let queried = super::last_glx_error(|| unsafe {
+            extra.GetMscRateOML(
+                self.display.inner.raw.cast(),
+                self.raw,
+                &mut numerator,
+                &mut denominator,
+            )
+        })
+        .ok()?;
+
+        if queried == 0 || numerator <= 0 || denominator <= 0 {
+            return None;
+        }
+
+        Some((numerator as u64 * 1000 / denominator as u64) as u32)
+    }
+
+    /// Make the surface a member of the swap `group`, where the buffer swaps
+    /// of all the members happen at the same time. Passing `0` removes the
+    /// surface from its current group.
+    ///
+    /// This requires `GLX_NV_swap_group`.
+    pub fn join_swap_group(&self, group: u32) -> Result<()> {
+        let extra = self.swap_group_extra()?;
+        let joined = super::last_glx_error(|| unsafe {
+            extra.JoinSwapGroupNV(self.display.inner.raw.cast(), self.raw, group)
+        })?;
+
+        if joined == 0 {
+            Err(ErrorKind::BadParameter.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Bind the swap `group` to the swap `barrier`, synchronizing the buffer
+    /// swaps across the groups bound to the same barrier. Passing `0` as the
+    /// `barrier` unbinds the group.
+    ///
+    /// This requires `GLX_NV_swap_group`.
+    pub fn bind_swap_barrier(&self, group: u32, barrier: u32) -> Result<()> {
+        let extra = self.swap_group_extra()?;
+        let bound = super::last_glx_error(|| unsafe {
+            extra.BindSwapBarrierNV(self.display.inner.raw.cast(), group, barrier)
+        })?;
+
+        if bound == 0 {
+            Err(ErrorKind::BadParameter.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn swap_group_extra(&self) -> Result<&'static GlxExtra> {
+        self.display
+            .inner
+            .glx_extra
+            .filter(|_| self.display.inner.client_extensions.contains("GLX_NV_swap_group"))
+            .ok_or_else(|| ErrorKind::NotSupported("swap groups are not supported").into())
+    }
+}
+
 impl<T: SurfaceTypeTrait> Drop for Surface<T> {
     fn drop(&mut self) {
         let _ = super::last_glx_error(|| unsafe {
@@ -205,12 +310,11 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     type SurfaceType = T;
 
     fn buffer_age(&self) -> u32 {
-        self.display
-            .inner
-            .client_extensions
-            .contains("GLX_EXT_buffer_age")
-            .then(|| unsafe { self.raw_attribute(glx_extra::BACK_BUFFER_AGE_EXT as c_int) })
-            .unwrap_or(0) as u32
+        if self.display.inner.client_extensions.contains("GLX_EXT_buffer_age") {
+            unsafe { self.raw_attribute(glx_extra::BACK_BUFFER_AGE_EXT as c_int) as u32 }
+        } else {
+            0
+        }
     }
 
     fn width(&self) -> Option<u32> {
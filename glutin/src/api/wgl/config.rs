@@ -2,6 +2,7 @@
 
 use std::io::Error as IoError;
 use std::mem::{self, MaybeUninit};
+use std::ops::RangeInclusive;
 use std::os::raw::c_int;
 use std::sync::Arc;
 use std::{fmt, iter};
@@ -13,6 +14,7 @@ use windows_sys::Win32::Graphics::OpenGL::{self as gl, PIXELFORMATDESCRIPTOR};
 
 use crate::config::{
     Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, RawConfig,
+    Transparency,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
@@ -74,6 +76,16 @@ impl Display {
             dw_flags |= gl::PFD_DRAW_TO_BITMAP;
         }
 
+        if template.native_renderable {
+            dw_flags |= gl::PFD_SUPPORT_GDI;
+        }
+
+        if template.srgb_capable {
+            return Err(
+                ErrorKind::NotSupported("sRGB framebuffers require WGL_ARB_pixel_format").into()
+            );
+        }
+
         dw_flags |= match template.stereoscopy {
             Some(true) => gl::PFD_STEREO,
             Some(false) => 0,
@@ -120,7 +132,7 @@ impl Display {
         unsafe {
             let pixel_format_index = gl::ChoosePixelFormat(hdc, &pixel_format_descriptor);
             if pixel_format_index == 0 {
-                return Err(ErrorKind::BadConfig.into());
+                return Err(ErrorKind::NoMatchingConfig.into());
             }
 
             let mut descriptor = MaybeUninit::<PIXELFORMATDESCRIPTOR>::uninit();
@@ -247,6 +259,23 @@ impl Display {
             attrs.push(1);
         }
 
+        if template.native_renderable {
+            attrs.push(wgl_extra::SUPPORT_GDI_ARB as c_int);
+            attrs.push(1);
+        }
+
+        if template.srgb_capable {
+            let extensions = &self.inner.client_extensions;
+            if extensions.contains(SRGB_EXT) || extensions.contains("WGL_EXT_colorspace") {
+                attrs.push(wgl_extra::FRAMEBUFFER_SRGB_CAPABLE_EXT as c_int);
+            } else if extensions.contains(SRGB_ARB) {
+                attrs.push(wgl_extra::FRAMEBUFFER_SRGB_CAPABLE_ARB as c_int);
+            } else {
+                return Err(ErrorKind::NotSupported("sRGB framebuffers are not supported").into());
+            }
+            attrs.push(1);
+        }
+
         // Terminate attrs with zero.
         attrs.push(0);
 
@@ -267,15 +296,20 @@ impl Display {
             }
             configs.set_len(num_configs as _);
 
-            Ok(Box::new(configs.into_iter().map(move |pixel_format_index| {
-                let inner = Arc::new(ConfigInner {
-                    display: self.clone(),
-                    hdc,
-                    pixel_format_index,
-                    descriptor: None,
-                });
-                Config { inner }
-            })))
+            let iter = configs
+                .into_iter()
+                .map(move |pixel_format_index| {
+                    let inner = Arc::new(ConfigInner {
+                        display: self.clone(),
+                        hdc,
+                        pixel_format_index,
+                        descriptor: None,
+                    });
+                    Config { inner }
+                })
+                .filter(move |config| template.fits_pbuffer_sizes(|| config.max_pbuffer_size()));
+
+            Ok(Box::new(iter))
         }
     }
 }
@@ -310,6 +344,11 @@ impl Config {
         }
     }
 
+    /// The index of the pixel format backing the config.
+    pub(crate) fn config_id(&self) -> u32 {
+        self.inner.pixel_format_index as u32
+    }
+
     pub(crate) fn is_single_buffered(&self) -> bool {
         match self.inner.descriptor.as_ref() {
             Some(descriptor) => (descriptor.dwFlags & gl::PFD_DOUBLEBUFFER) == 0,
@@ -422,6 +461,11 @@ impl GlConfig for Config {
                 if self.raw_attribute(wgl_extra::DRAW_TO_BITMAP_ARB as c_int) != 0 {
                     flags |= ConfigSurfaceTypes::WINDOW
                 }
+                if self.inner.display.inner.client_extensions.contains("WGL_ARB_pbuffer")
+                    && self.raw_attribute(wgl_extra::DRAW_TO_PBUFFER_ARB as c_int) != 0
+                {
+                    flags |= ConfigSurfaceTypes::PBUFFER
+                }
             },
         }
 
@@ -447,6 +491,20 @@ impl GlConfig for Config {
         }
     }
 
+    fn transparency(&self) -> Option<Transparency> {
+        if !self.supports_transparency()? {
+            return Some(Transparency::Opaque);
+        }
+
+        unsafe {
+            Some(Transparency::ChromaKey {
+                r: self.raw_attribute(wgl_extra::TRANSPARENT_RED_VALUE_ARB as c_int) as u32,
+                g: self.raw_attribute(wgl_extra::TRANSPARENT_GREEN_VALUE_ARB as c_int) as u32,
+                b: self.raw_attribute(wgl_extra::TRANSPARENT_BLUE_VALUE_ARB as c_int) as u32,
+            })
+        }
+    }
+
     fn api(&self) -> Api {
         let mut api = Api::OPENGL;
         if self.inner.display.inner.features.contains(DisplayFeatures::CREATE_ES_CONTEXT) {
@@ -455,6 +513,36 @@ impl GlConfig for Config {
 
         api
     }
+
+    fn conformant_apis(&self) -> Option<Api> {
+        None
+    }
+
+    fn max_pbuffer_size(&self) -> Option<(u32, u32, u32)> {
+        if self.inner.descriptor.is_some()
+            || !self.inner.display.inner.client_extensions.contains("WGL_ARB_pbuffer")
+        {
+            return None;
+        }
+
+        unsafe {
+            let width = self.raw_attribute(wgl_extra::MAX_PBUFFER_WIDTH_ARB as c_int) as u32;
+            let height = self.raw_attribute(wgl_extra::MAX_PBUFFER_HEIGHT_ARB as c_int) as u32;
+            let pixels = self.raw_attribute(wgl_extra::MAX_PBUFFER_PIXELS_ARB as c_int) as u32;
+            Some((width, height, pixels))
+        }
+    }
+
+    fn native_renderable(&self) -> Option<bool> {
+        match self.inner.descriptor.as_ref() {
+            Some(descriptor) => Some(descriptor.dwFlags & gl::PFD_SUPPORT_GDI != 0),
+            _ => unsafe { Some(self.raw_attribute(wgl_extra::SUPPORT_GDI_ARB as c_int) != 0) },
+        }
+    }
+
+    fn swap_interval_range(&self) -> Option<RangeInclusive<u32>> {
+        None
+    }
 }
 
 impl GetGlDisplay for Config {
@@ -13,8 +13,8 @@ use windows_sys::Win32::Graphics::Gdi::{self as gdi, HDC};
 
 use crate::config::GetGlConfig;
 use crate::context::{
-    self, AsRawContext, ContextApi, ContextAttributes, GlProfile, Priority, RawContext,
-    ReleaseBehavior, Robustness, Version,
+    self, AsRawContext, ContextApi, ContextAttributes, CreationAttributes, DebugCallbackSlot,
+    GlProfile, Priority, RawContext, ReleaseBehavior, Robustness, Version,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
@@ -32,6 +32,20 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        context_attributes.with_robustness_fallback(|context_attributes| unsafe {
+            self.create_context_with_attributes(config, context_attributes)
+        })
+    }
+
+    unsafe fn create_context_with_attributes(
+        &self,
+        config: &Config,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext> {
+        if context_attributes.protected {
+            return Err(ErrorKind::NotSupported("protected content is not supported").into());
+        }
+
         let hdc = match context_attributes.raw_window_handle.as_ref() {
             handle @ Some(RawWindowHandle::Win32(window)) => unsafe {
                 let _ = config.apply_on_native_window(handle.unwrap());
@@ -42,27 +56,40 @@ impl Display {
 
         let share_ctx = match context_attributes.shared_context {
             Some(RawContext::Wgl(share)) => share,
-            _ => std::ptr::null(),
+            None => std::ptr::null(),
+            #[allow(unreachable_patterns)]
+            _ => return Err(ErrorKind::NotSupported("incompatible context was passed").into()),
         };
 
-        let (context, supports_surfaceless) =
-            if self.inner.client_extensions.contains("WGL_ARB_create_context") {
-                self.create_context_arb(hdc, share_ctx, context_attributes)?
-            } else {
-                unsafe {
-                    let raw = wgl::CreateContext(hdc as *const _);
-                    if raw.is_null() {
-                        return Err(IoError::last_os_error().into());
-                    }
+        if context_attributes.priority.is_some() {
+            context_attributes.ignore_attribute("context priority is not supported")?;
+        }
 
-                    // Context sharing.
-                    if !share_ctx.is_null() && wgl::ShareLists(share_ctx, raw) == 0 {
-                        return Err(IoError::last_os_error().into());
-                    }
+        let supports_arb = self.inner.client_extensions.contains("WGL_ARB_create_context");
+        let legacy_ignored = context_attributes.api.is_some()
+            || context_attributes.profile.is_some()
+            || context_attributes.debug;
+        if legacy_ignored && !supports_arb {
+            context_attributes.ignore_attribute("WGL_ARB_create_context is not supported")?;
+        }
 
-                    (WglContext(raw), false)
+        let (context, supports_surfaceless) = if supports_arb {
+            self.create_context_arb(hdc, share_ctx, context_attributes)?
+        } else {
+            unsafe {
+                let raw = wgl::CreateContext(hdc as *const _);
+                if raw.is_null() {
+                    return Err(IoError::last_os_error().into());
                 }
-            };
+
+                // Context sharing.
+                if !share_ctx.is_null() && wgl::ShareLists(share_ctx, raw) == 0 {
+                    return Err(IoError::last_os_error().into());
+                }
+
+                (WglContext(raw), false)
+            }
+        };
 
         let config = config.clone();
         let is_gles = matches!(context_attributes.api, Some(ContextApi::Gles(_)));
@@ -72,6 +99,14 @@ impl Display {
             raw: context,
             is_gles,
             supports_surfaceless,
+            // The legacy context creation can't request the robustness.
+            robustness: if supports_arb {
+                context_attributes.robustness
+            } else {
+                Robustness::NotRobust
+            },
+            attributes: CreationAttributes::new(context_attributes),
+            debug_callback: Default::default(),
         };
         Ok(NotCurrentContext { inner })
     }
@@ -145,12 +180,13 @@ impl Display {
         if self.inner.features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS) {
             match context_attributes.robustness {
                 Robustness::NotRobust => (),
-                Robustness::RobustNoResetNotification => {
+                Robustness::RobustNoResetNotification
+                | Robustness::TryRobustNoResetNotification => {
                     attrs.push(wgl_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
                     attrs.push(wgl_extra::NO_RESET_NOTIFICATION_ARB as c_int);
                     flags |= wgl_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
                 },
-                Robustness::RobustLoseContextOnReset => {
+                Robustness::RobustLoseContextOnReset | Robustness::TryRobustLoseContextOnReset => {
                     attrs.push(wgl_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
                     attrs.push(wgl_extra::LOSE_CONTEXT_ON_RESET_ARB as c_int);
                     flags |= wgl_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
@@ -178,6 +214,8 @@ impl Display {
         // Debug flag.
         if context_attributes.debug && !requested_no_error {
             flags |= wgl_extra::CONTEXT_DEBUG_BIT_ARB as c_int;
+        } else if context_attributes.debug {
+            context_attributes.ignore_attribute("debug context is not supported")?;
         }
 
         if flags != 0 {
@@ -272,6 +310,10 @@ impl GlContext for NotCurrentContext {
     fn priority(&self) -> Priority {
         Priority::Medium
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl GetGlDisplay for NotCurrentContext {
@@ -348,6 +390,45 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
 
 impl Sealed for PossiblyCurrentContext {}
 
+impl PossiblyCurrentContext {
+    pub(crate) fn context_attributes(&self) -> &ContextAttributes {
+        &self.inner.attributes
+    }
+
+    pub(crate) fn debug_callback(&self) -> &DebugCallbackSlot {
+        &self.inner.debug_callback
+    }
+
+    /// Save the context current on the thread.
+    pub(crate) fn save_current(&self) -> CurrentState {
+        unsafe { CurrentState { hdc: wgl::GetCurrentDC(), context: wgl::GetCurrentContext() } }
+    }
+
+    /// Restore the state saved with [`Self::save_current`].
+    pub(crate) fn restore_current(&self, state: &CurrentState) -> Result<()> {
+        if state.context.is_null() {
+            return self.make_not_current_in_place();
+        }
+
+        unsafe {
+            if wgl::MakeCurrent(state.hdc, state.context) == 0 {
+                Err(IoError::last_os_error().into())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The current context of the thread saved by the [`CurrentContextGuard`].
+///
+/// [`CurrentContextGuard`]: crate::context::CurrentContextGuard
+#[derive(Debug)]
+pub(crate) struct CurrentState {
+    hdc: wgl::types::HDC,
+    context: HGLRC,
+}
+
 impl GetGlDisplay for PossiblyCurrentContext {
     type Target = Display;
 
@@ -372,6 +453,10 @@ impl GlContext for PossiblyCurrentContext {
     fn priority(&self) -> Priority {
         Priority::Medium
     }
+
+    fn robustness(&self) -> Robustness {
+        self.inner.robustness
+    }
 }
 
 impl AsRawContext for PossiblyCurrentContext {
@@ -386,6 +471,9 @@ struct ContextInner {
     raw: WglContext,
     is_gles: bool,
     supports_surfaceless: bool,
+    robustness: Robustness,
+    attributes: CreationAttributes,
+    debug_callback: DebugCallbackSlot,
 }
 
 impl fmt::Debug for ContextInner {
@@ -144,6 +144,6 @@ unsafe fn load_extra_functions(
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
         let raw = error.raw_os_error().map(|code| code as i64);
-        Error::new(raw, Some(error.to_string()), ErrorKind::Misc)
+        Error::new(raw, Some(error.to_string()), ErrorKind::Misc).record_native("WGL")
     }
 }
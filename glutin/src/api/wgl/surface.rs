@@ -4,6 +4,7 @@ use std::io::Error as IoError;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
 use std::os::raw::c_int;
+use std::sync::atomic::AtomicBool;
 use std::{fmt, mem};
 
 use glutin_wgl_sys::wgl::types::GLenum;
@@ -13,7 +14,7 @@ use raw_window_handle::RawWindowHandle;
 use windows_sys::Win32::Foundation::{HWND, RECT};
 use windows_sys::Win32::Graphics::Gdi::HDC;
 use windows_sys::Win32::Graphics::{Gdi as gdi, OpenGL as gl};
-use windows_sys::Win32::UI::WindowsAndMessaging::GetClientRect;
+use windows_sys::Win32::UI::WindowsAndMessaging::{GetClientRect, IsWindow};
 
 use crate::config::GetGlConfig;
 use crate::display::{DisplayFeatures, GetGlDisplay};
@@ -28,6 +29,7 @@ use crate::surface::{
 use super::config::Config;
 use super::context::PossiblyCurrentContext;
 use super::display::Display;
+use super::WglExtra;
 
 impl Display {
     pub(crate) unsafe fn create_pixmap_surface(
@@ -43,12 +45,17 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<PbufferSurface>,
     ) -> Result<Surface<PbufferSurface>> {
+        surface_attributes.check_protected_unsupported()?;
+
         let extra = self
             .inner
             .wgl_extra
             .filter(|_| self.inner.client_extensions.contains("WGL_ARB_pbuffer"))
             .ok_or(ErrorKind::NotSupported("pbuffer extensions are not supported"))?;
 
+        SurfaceAttributes::check_pbuffer_samples(config)?;
+        surface_attributes.check_max_pbuffer_size(config.max_pbuffer_size())?;
+
         let hdc = config.inner.hdc;
         let width = surface_attributes.width.unwrap().get() as c_int;
         let height = surface_attributes.height.unwrap().get() as c_int;
@@ -80,6 +87,7 @@ impl Display {
             display: self.clone(),
             config: config.clone(),
             raw: WglSurface::PBuffer(hbuf, hdc as _),
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         };
 
@@ -91,6 +99,8 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<WindowSurface>,
     ) -> Result<Surface<WindowSurface>> {
+        surface_attributes.check_protected_unsupported()?;
+
         let hwnd = match surface_attributes.raw_window_handle.as_ref().unwrap() {
             handle @ RawWindowHandle::Win32(window_handle) => {
                 let _ = unsafe { config.apply_on_native_window(handle) };
@@ -109,6 +119,7 @@ impl Display {
             display: self.clone(),
             config: config.clone(),
             raw: WglSurface::Window(hwnd, hdc),
+            swap_interval_set: AtomicBool::new(false),
             _ty: PhantomData,
         };
 
@@ -121,6 +132,7 @@ pub struct Surface<T: SurfaceTypeTrait> {
     display: Display,
     config: Config,
     pub(crate) raw: WglSurface,
+    pub(crate) swap_interval_set: AtomicBool,
     _ty: PhantomData<T>,
 }
 
@@ -128,6 +140,24 @@ pub struct Surface<T: SurfaceTypeTrait> {
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
 impl<T: SurfaceTypeTrait> Surface<T> {
+    /// The `HDC` used by the surface for rendering.
+    ///
+    /// The device context is owned by the surface and must not be released.
+    pub fn hdc(&self) -> *const std::ffi::c_void {
+        self.raw.hdc() as _
+    }
+
+    /// Whether the surface is still usable, i.e. its window wasn't destroyed
+    /// and its pbuffer wasn't lost due to the display mode change.
+    pub fn is_valid(&self) -> bool {
+        match self.raw {
+            WglSurface::Window(hwnd, _) => unsafe { IsWindow(hwnd) != 0 },
+            WglSurface::PBuffer(..) => {
+                self.raw_attribute(wgl_extra::PBUFFER_LOST_ARB).is_some_and(|lost| lost == 0)
+            },
+        }
+    }
+
     fn raw_attribute(&self, attr: GLenum) -> Option<c_int> {
         match self.raw {
             WglSurface::Window(..) => None,
@@ -144,6 +174,44 @@ impl<T: SurfaceTypeTrait> Surface<T> {
     }
 }
 
+impl Surface<WindowSurface> {
+    /// Make the surface a member of the swap `group`, where the buffer swaps
+    /// of all the members happen at the same time. Passing `0` removes the
+    /// surface from its current group.
+    ///
+    /// This requires `WGL_NV_swap_group`.
+    pub fn join_swap_group(&self, group: u32) -> Result<()> {
+        let extra = self.swap_group_extra()?;
+        if unsafe { extra.JoinSwapGroupNV(self.raw.hdc() as _, group) } == 0 {
+            Err(IoError::last_os_error().into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Bind the swap `group` to the swap `barrier`, synchronizing the buffer
+    /// swaps across the groups bound to the same barrier. Passing `0` as the
+    /// `barrier` unbinds the group.
+    ///
+    /// This requires `WGL_NV_swap_group`.
+    pub fn bind_swap_barrier(&self, group: u32, barrier: u32) -> Result<()> {
+        let extra = self.swap_group_extra()?;
+        if unsafe { extra.BindSwapBarrierNV(group, barrier) } == 0 {
+            Err(IoError::last_os_error().into())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn swap_group_extra(&self) -> Result<&'static WglExtra> {
+        self.display
+            .inner
+            .wgl_extra
+            .filter(|_| self.display.inner.client_extensions.contains("WGL_NV_swap_group"))
+            .ok_or_else(|| ErrorKind::NotSupported("swap groups are not supported").into())
+    }
+}
+
 impl<T: SurfaceTypeTrait> Drop for Surface<T> {
     fn drop(&mut self) {
         unsafe {
@@ -1,7 +1,10 @@
 //! Api config picking and creating utils.
 #![allow(unreachable_patterns)]
 
+use std::collections::HashSet;
+use std::fmt;
 use std::num::NonZeroU32;
+use std::ops::RangeInclusive;
 
 use bitflags::bitflags;
 use raw_window_handle::RawWindowHandle;
@@ -10,7 +13,7 @@ use crate::display::{Display, GetGlDisplay};
 use crate::private::{gl_api_dispatch, Sealed};
 
 #[cfg(x11_platform)]
-use crate::platform::x11::{X11GlConfigExt, X11VisualInfo};
+use crate::platform::x11::{X11GlConfigExt, X11VisualInfo, X11VisualType};
 
 #[cfg(cgl_backend)]
 use crate::api::cgl::config::Config as CglConfig;
@@ -56,6 +59,19 @@ pub trait GlConfig: Sealed {
     /// identified, in that case transparent window could still work.
     fn supports_transparency(&self) -> Option<bool>;
 
+    /// The kind of transparency supported by the config.
+    ///
+    /// Unlike [`Self::supports_transparency`] this tells apart the chroma-key
+    /// transparency, which can't be used for alpha compositing.
+    ///
+    /// This function will return `None` when the property couldn't be
+    /// identified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL:** [`Transparency::AlphaBlend`] is never reported.
+    fn transparency(&self) -> Option<Transparency>;
+
     /// Whether the config is hardware accelerated.
     ///
     /// The meaning of this may vary from system to system. On some it could
@@ -68,7 +84,63 @@ pub trait GlConfig: Sealed {
     fn config_surface_types(&self) -> ConfigSurfaceTypes;
 
     /// The [`crate::config::Api`] supported by the configuration.
+    ///
+    /// The contexts could only be created for the supported Api, so the
+    /// configs should be filtered by it before picking.
     fn api(&self) -> Api;
+
+    /// The [`crate::config::Api`]s for which the configuration passes the
+    /// conformance tests.
+    ///
+    /// An [`Api`] missing from the result, while present in [`Self::api`],
+    /// means that rendering with it may deviate from the specification.
+    ///
+    /// This function will return `None` when the property couldn't be
+    /// identified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **GLX:** the result is either [`Self::api`] or empty, based on the
+    ///   `GLX_CONFIG_CAVEAT`.
+    /// - **WGL/CGL:** always returns `None`.
+    fn conformant_apis(&self) -> Option<Api>;
+
+    /// The maximum pbuffer `(width, height, pixels)` supported by the config.
+    ///
+    /// Creating a pbuffer exceeding any of these limits will fail, unless the
+    /// largest pbuffer is requested.
+    ///
+    /// This function will return `None` when the property couldn't be
+    /// identified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL:** requires `WGL_ARB_pbuffer`.
+    /// - **CGL:** always returns `None`.
+    fn max_pbuffer_size(&self) -> Option<(u32, u32, u32)>;
+
+    /// Whether the native rendering Api, like Xlib or GDI, could draw into
+    /// the surfaces created with this config alongside the GL.
+    ///
+    /// This function will return `None` when the property couldn't be
+    /// identified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL:** reports the GDI support.
+    /// - **GLX/CGL:** always returns `None`.
+    fn native_renderable(&self) -> Option<bool>;
+
+    /// The range of the swap intervals the surfaces created with this config
+    /// could use.
+    ///
+    /// This function will return `None` when the property couldn't be
+    /// identified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **GLX/WGL/CGL:** always returns `None`.
+    fn swap_interval_range(&self) -> Option<RangeInclusive<u32>>;
 }
 
 /// The trait to
@@ -173,7 +245,7 @@ impl ConfigTemplateBuilder {
     /// # Api-specific
     ///
     /// - **EGL:** [`Api::GLES2`] bit is set by default to avoid matching
-    ///   [`Api::GLES1`] configs;
+    ///   [`Api::GLES1`] configs, [`Api::OPENVG`] is ignored;
     /// - **GLX/WGL/CGL:** [`Api::OPENGL`] is always present in the result.
     #[inline]
     pub fn with_api(mut self, api: Api) -> Self {
@@ -181,6 +253,21 @@ impl ConfigTemplateBuilder {
         self
     }
 
+    /// Require the config to be conformant with the given [`Api`]s.
+    ///
+    /// By default conformance isn't required.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** maps to `EGL_CONFORMANT`;
+    /// - **GLX/WGL/CGL:** not supported, use [`GlConfig::conformant_apis`] to
+    ///   filter the configs.
+    #[inline]
+    pub fn with_conformant_api(mut self, api: Api) -> Self {
+        self.template.conformant_api = Some(api);
+        self
+    }
+
     /// Whether the stereo pairs should be present.
     ///
     /// By default it isn't specified.
@@ -213,7 +300,59 @@ impl ConfigTemplateBuilder {
         self
     }
 
+    /// Whether the config should allow the native rendering Api, like Xlib or
+    /// GDI, to draw into the surfaces alongside the GL.
+    ///
+    /// The default is `false`, meaning that it's not requested.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL:** the GDI support is requested.
+    /// - **GLX/CGL:** not supported, use [`GlConfig::native_renderable`] to
+    ///   filter the configs.
+    #[inline]
+    pub fn with_native_renderable(mut self, native_renderable: bool) -> Self {
+        self.template.native_renderable = native_renderable;
+        self
+    }
+
+    /// Whether the config should support the sRGB framebuffers, see
+    /// [`GlConfig::srgb_capable`].
+    ///
+    /// The default is `false`, meaning that it's not requested.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** the sRGB support is per display, so either all or none of
+    ///   the configs are sRGB capable.
+    /// - **WGL:** requires `WGL_ARB_framebuffer_sRGB` or
+    ///   `WGL_EXT_framebuffer_sRGB`.
+    /// - **CGL:** the configs are always sRGB capable.
+    #[inline]
+    pub fn with_srgb(mut self, srgb_capable: bool) -> Self {
+        self.template.srgb_capable = srgb_capable;
+        self
+    }
+
+    /// The class of the X11 visual the config should use.
+    ///
+    /// The default is [`X11VisualType::TrueColor`].
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** not supported, use [`X11GlConfigExt::x11_visual_type`] to
+    ///   filter the configs.
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn with_x11_visual_type(mut self, x11_visual_type: X11VisualType) -> Self {
+        self.template.x11_visual_type = x11_visual_type;
+        self
+    }
+
     /// With the maximum sizes of pbuffer.
+    ///
+    /// The configs which can't back the pbuffer of at least this size, as
+    /// reported by [`GlConfig::max_pbuffer_size`], are filtered out.
     #[inline]
     pub fn with_pbuffer_sizes(mut self, width: NonZeroU32, height: NonZeroU32) -> Self {
         self.template.max_pbuffer_width = Some(width.into());
@@ -263,6 +402,26 @@ impl ConfigTemplateBuilder {
         self
     }
 
+    /// Require the config to support the swap interval of at least
+    /// `min_swap_interval_max`, e.g. `1` to reject the configs which can't
+    /// wait for the vertical blank.
+    ///
+    /// Unlike the `max_swap_interval` of the
+    /// [`ConfigTemplateBuilder::with_swap_interval`] which is matched
+    /// exactly, any config with the greater maximum swap interval is
+    /// accepted.
+    ///
+    /// By default the value isn't specified.
+    ///
+    /// # Api-specific
+    ///
+    /// Only supported with `EGL`.
+    #[inline]
+    pub fn with_min_swap_interval_max(mut self, min_swap_interval_max: u32) -> Self {
+        self.template.min_swap_interval_max = Some(min_swap_interval_max);
+        self
+    }
+
     /// Build the template to match the configs against.
     #[must_use]
     pub fn build(self) -> ConfigTemplate {
@@ -294,15 +453,27 @@ pub struct ConfigTemplate {
     /// The maximum swap interval supported by the configuration.
     pub(crate) max_swap_interval: Option<u16>,
 
+    /// The lower bound of the maximum swap interval.
+    pub(crate) min_swap_interval_max: Option<u32>,
+
     /// The types of the surfaces supported by the configuration.
     pub(crate) config_surface_types: ConfigSurfaceTypes,
 
     /// The rendering Api's supported by the configuration.
     pub(crate) api: Option<Api>,
 
+    /// The rendering Api's the configuration must be conformant with.
+    pub(crate) conformant_api: Option<Api>,
+
     /// The config should support transparency.
     pub(crate) transparency: bool,
 
+    /// The config should support the native rendering.
+    pub(crate) native_renderable: bool,
+
+    /// The config should support the sRGB framebuffers.
+    pub(crate) srgb_capable: bool,
+
     /// The config should prefer single buffering.
     pub(crate) single_buffering: bool,
 
@@ -323,6 +494,64 @@ pub struct ConfigTemplate {
 
     /// The native window config should support rendering into.
     pub(crate) native_window: Option<RawWindowHandle>,
+
+    /// The class of the X11 visual.
+    #[cfg(x11_platform)]
+    pub(crate) x11_visual_type: X11VisualType,
+}
+
+impl ConfigTemplate {
+    /// The requested type of the color buffer.
+    pub fn color_buffer_type(&self) -> ColorBufferType {
+        self.color_buffer_type
+    }
+
+    /// The requested bits of alpha in the color buffer.
+    pub fn alpha_size(&self) -> u8 {
+        self.alpha_size
+    }
+
+    /// The requested bits of depth in the depth buffer.
+    pub fn depth_size(&self) -> u8 {
+        self.depth_size
+    }
+
+    /// The requested bits of stencil in the stencil buffer.
+    pub fn stencil_size(&self) -> u8 {
+        self.stencil_size
+    }
+
+    /// The requested amount of samples in the multisample buffer.
+    pub fn num_samples(&self) -> Option<u8> {
+        self.num_samples
+    }
+
+    /// Whether the config should support transparency.
+    pub fn transparency(&self) -> bool {
+        self.transparency
+    }
+
+    /// Whether the config with the given `max_pbuffer_size` could back the
+    /// pbuffer of the requested size.
+    ///
+    /// The `max_pbuffer_size` is only queried when some size was requested.
+    #[cfg(any(egl_backend, glx_backend, wgl_backend))]
+    pub(crate) fn fits_pbuffer_sizes(
+        &self,
+        max_pbuffer_size: impl FnOnce() -> Option<(u32, u32, u32)>,
+    ) -> bool {
+        if self.max_pbuffer_width.is_none() && self.max_pbuffer_height.is_none() {
+            return true;
+        }
+
+        let (max_width, max_height, _) = match max_pbuffer_size() {
+            Some(max_pbuffer_size) => max_pbuffer_size,
+            None => return true,
+        };
+
+        self.max_pbuffer_width.map_or(true, |width| width <= max_width)
+            && self.max_pbuffer_height.map_or(true, |height| height <= max_height)
+    }
 }
 
 impl Default for ConfigTemplate {
@@ -340,12 +569,18 @@ impl Default for ConfigTemplate {
 
             transparency: false,
 
+            native_renderable: false,
+
+            srgb_capable: false,
+
             stereoscopy: None,
 
             min_swap_interval: None,
 
             max_swap_interval: None,
 
+            min_swap_interval_max: None,
+
             single_buffering: false,
 
             float_pixels: false,
@@ -358,7 +593,11 @@ impl Default for ConfigTemplate {
             native_window: None,
             hardware_accelerated: None,
 
+            #[cfg(x11_platform)]
+            x11_visual_type: X11VisualType::TrueColor,
+
             api: None,
+            conformant_api: None,
         }
     }
 }
@@ -393,11 +632,17 @@ bitflags! {
 
         /// Context supports OpenGL ES 3 API.
         const GLES3  = 0b00001000;
+
+        /// Context supports OpenVG API.
+        ///
+        /// Only reported by EGL, glutin can't create OpenVG contexts, thus
+        /// it's ignored when matching the configs.
+        const OPENVG = 0b00010000;
     }
 }
 
 /// The buffer type baked by the config.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ColorBufferType {
     /// The backing buffer is using RGB format.
     Rgb {
@@ -413,6 +658,27 @@ pub enum ColorBufferType {
     Luminance(u8),
 }
 
+/// The kind of transparency supported by the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transparency {
+    /// The surfaces created with the config are opaque.
+    Opaque,
+
+    /// The alpha channel is used to blend the surface with what is behind it.
+    AlphaBlend,
+
+    /// The pixels matching the given color are transparent, while the rest
+    /// are opaque.
+    ChromaKey {
+        /// The red component of the transparent color.
+        r: u32,
+        /// The green component of the transparent color.
+        g: u32,
+        /// The blue component of the transparent color.
+        b: u32,
+    },
+}
+
 /// The GL configuration used to create [`Surface`] and [`Context`] in a cross
 /// platform way.
 ///
@@ -427,7 +693,7 @@ pub enum ColorBufferType {
 ///
 /// [`Surface`]: crate::surface::Surface
 /// [`Context`]: crate::context::NotCurrentContext
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum Config {
     /// The EGL config.
     #[cfg(egl_backend)]
@@ -487,9 +753,123 @@ impl GlConfig for Config {
         gl_api_dispatch!(self; Self(config) => config.supports_transparency())
     }
 
+    fn transparency(&self) -> Option<Transparency> {
+        gl_api_dispatch!(self; Self(config) => config.transparency())
+    }
+
     fn api(&self) -> Api {
         gl_api_dispatch!(self; Self(config) => config.api())
     }
+
+    fn conformant_apis(&self) -> Option<Api> {
+        gl_api_dispatch!(self; Self(config) => config.conformant_apis())
+    }
+
+    fn native_renderable(&self) -> Option<bool> {
+        gl_api_dispatch!(self; Self(config) => config.native_renderable())
+    }
+
+    fn max_pbuffer_size(&self) -> Option<(u32, u32, u32)> {
+        gl_api_dispatch!(self; Self(config) => config.max_pbuffer_size())
+    }
+
+    fn swap_interval_range(&self) -> Option<RangeInclusive<u32>> {
+        gl_api_dispatch!(self; Self(config) => config.swap_interval_range())
+    }
+}
+
+impl Config {
+    /// The name of the Api backing the config.
+    fn api_name(&self) -> &'static str {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(_) => "EGL",
+            #[cfg(glx_backend)]
+            Self::Glx(_) => "GLX",
+            #[cfg(wgl_backend)]
+            Self::Wgl(_) => "WGL",
+            #[cfg(cgl_backend)]
+            Self::Cgl(_) => "CGL",
+        }
+    }
+
+    /// The platform identifier of the config, if the platform has one.
+    fn config_id(&self) -> Option<u32> {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(config) => Some(config.config_id()),
+            #[cfg(glx_backend)]
+            Self::Glx(config) => Some(config.config_id()),
+            #[cfg(wgl_backend)]
+            Self::Wgl(config) => Some(config.config_id()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(self.api_name())
+            .field("id", &self.config_id())
+            .field("color_buffer_type", &self.color_buffer_type())
+            .field("float_pixels", &self.float_pixels())
+            .field("alpha_size", &self.alpha_size())
+            .field("depth_size", &self.depth_size())
+            .field("stencil_size", &self.stencil_size())
+            .field("num_samples", &self.num_samples())
+            .field("srgb_capable", &self.srgb_capable())
+            .field("transparency", &self.transparency())
+            .finish()
+    }
+}
+
+/// Compact form, like `EGL config 5: rgba8888, depth 24, stencil 8, 4
+/// samples, srgb`.
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} config", self.api_name())?;
+        if let Some(id) = self.config_id() {
+            write!(f, " {id}")?;
+        }
+
+        let alpha = self.alpha_size();
+        match self.color_buffer_type() {
+            Some(ColorBufferType::Rgb { r_size, g_size, b_size }) if alpha > 0 => {
+                write!(f, ": rgba{r_size}{g_size}{b_size}{alpha}")?
+            },
+            Some(ColorBufferType::Rgb { r_size, g_size, b_size }) => {
+                write!(f, ": rgb{r_size}{g_size}{b_size}")?
+            },
+            Some(ColorBufferType::Luminance(size)) if alpha > 0 => {
+                write!(f, ": luminance{size} alpha{alpha}")?
+            },
+            Some(ColorBufferType::Luminance(size)) => write!(f, ": luminance{size}")?,
+            None => write!(f, ": unknown color buffer")?,
+        }
+
+        if self.float_pixels() {
+            f.write_str(" float")?;
+        }
+
+        write!(f, ", depth {}, stencil {}", self.depth_size(), self.stencil_size())?;
+
+        match self.num_samples() {
+            0 | 1 => (),
+            samples => write!(f, ", {samples} samples")?,
+        }
+
+        if self.srgb_capable() {
+            f.write_str(", srgb")?;
+        }
+
+        match self.transparency() {
+            Some(Transparency::AlphaBlend) => f.write_str(", transparent")?,
+            Some(Transparency::ChromaKey { r, g, b }) => write!(f, ", chroma key ({r}, {g}, {b})")?,
+            Some(Transparency::Opaque) | None => (),
+        }
+
+        Ok(())
+    }
 }
 
 impl GetGlDisplay for Config {
@@ -509,6 +889,35 @@ impl X11GlConfigExt for Config {
 
 impl Sealed for Config {}
 
+/// Collapse the configs which are identical in the color buffer type and its
+/// float pixels, alpha, depth and stencil sizes, number of samples, sRGB
+/// capability, transparency and the supported Api, keeping the first config
+/// of each group.
+///
+/// The order of the configs is preserved, so it could be applied to the
+/// [`GlDisplay::find_configs`] output before picking the config.
+///
+/// [`GlDisplay::find_configs`]: crate::display::GlDisplay::find_configs
+pub fn dedupe_configs(configs: impl IntoIterator<Item = Config>) -> Vec<Config> {
+    let mut seen = HashSet::new();
+    configs
+        .into_iter()
+        .filter(|config| {
+            seen.insert((
+                config.color_buffer_type(),
+                config.float_pixels(),
+                config.alpha_size(),
+                config.depth_size(),
+                config.stencil_size(),
+                config.num_samples(),
+                config.srgb_capable(),
+                config.transparency(),
+                config.api(),
+            ))
+        })
+        .collect()
+}
+
 /// Raw config.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RawConfig {
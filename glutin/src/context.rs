@@ -1,15 +1,20 @@
 //! OpenGL context creation and initialization.
 
 #![allow(unreachable_patterns)]
-use std::ffi;
+use std::borrow::Cow;
+use std::ffi::{self, CStr};
+use std::fmt;
+use std::ops::Deref;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
 
 use raw_window_handle::RawWindowHandle;
 
 use crate::config::{Config, GetGlConfig};
-use crate::display::{Display, GetGlDisplay};
-use crate::error::Result;
+use crate::display::{Display, GetGlDisplay, GlDisplay};
+use crate::error::{ErrorKind, Result};
 use crate::private::{gl_api_dispatch, Sealed};
-use crate::surface::{GlSurface, Surface, SurfaceTypeTrait};
+use crate::surface::{GlSurface, Surface, SurfaceTypeTrait, SwapInterval, WindowSurface};
 
 #[cfg(cgl_backend)]
 use crate::api::cgl::context::{
@@ -19,6 +24,8 @@ use crate::api::cgl::context::{
 use crate::api::egl::context::{
     NotCurrentContext as NotCurrentEglContext, PossiblyCurrentContext as PossiblyCurrentEglContext,
 };
+#[cfg(egl_backend)]
+use crate::api::egl::sync::Fence as EglFence;
 #[cfg(glx_backend)]
 use crate::api::glx::context::{
     NotCurrentContext as NotCurrentGlxContext, PossiblyCurrentContext as PossiblyCurrentGlxContext,
@@ -27,9 +34,31 @@ use crate::api::glx::context::{
 use crate::api::wgl::context::{
     NotCurrentContext as NotCurrentWglContext, PossiblyCurrentContext as PossiblyCurrentWglContext,
 };
+#[cfg(egl_backend)]
+use crate::display::GetDisplayExtensions;
 
 /// A trait to group common context operations.
-pub trait GlContext: Sealed {
+///
+/// The trait is implemented by both the not current and possibly current
+/// contexts, so it could be used to write the code generic over the context
+/// state, while the state dependent operations are left to
+/// [`NotCurrentGlContext`] and [`PossiblyCurrentGlContext`].
+///
+/// ```no_run
+/// use glutin::config::GlConfig;
+/// use glutin::context::GlContext;
+/// use glutin::display::GlDisplay;
+///
+/// fn describe(context: &impl GlContext) -> String {
+///     format!(
+///         "{:?} on {} with {} samples",
+///         context.context_api(),
+///         context.display().version_string(),
+///         context.config().num_samples()
+///     )
+/// }
+/// ```
+pub trait GlContext: Sealed + GetGlConfig + GetGlDisplay + AsRawContext {
     /// Get the [`ContextApi`] used by the context.
     ///
     /// The returned value's [`Version`] will always be `None`.
@@ -37,6 +66,16 @@ pub trait GlContext: Sealed {
 
     /// Get the [`Priority`] used by the context.
     fn priority(&self) -> Priority;
+
+    /// Get the [`Robustness`] used by the context.
+    ///
+    /// When [`Robustness::TryRobustNoResetNotification`] or
+    /// [`Robustness::TryRobustLoseContextOnReset`] was requested, this reports
+    /// the robustness actually obtained, which is either the robust variant or
+    /// [`Robustness::NotRobust`]. The [`Robustness::NotRobust`] is also
+    /// reported when the context was created without the robustness attributes,
+    /// e.g. with the legacy context creation on GLX and WGL.
+    fn robustness(&self) -> Robustness;
 }
 
 /// A trait to group common not current operations.
@@ -70,9 +109,14 @@ pub trait NotCurrentGlContext: Sealed {
     /// The same as [`Self::make_current`], but provides a way to set draw and
     /// read surfaces.
     ///
+    /// The support could be checked up front with
+    /// [`DisplayFeatures::SEPARATE_READ_SURFACE`].
+    ///
     /// # Api specific
     ///
     /// - **WGL/CGL:** not supported.
+    ///
+    /// [`DisplayFeatures::SEPARATE_READ_SURFACE`]: crate::display::DisplayFeatures::SEPARATE_READ_SURFACE
     fn make_current_draw_read<T: SurfaceTypeTrait>(
         self,
         surface_draw: &Self::Surface<T>,
@@ -111,6 +155,11 @@ pub trait PossiblyCurrentGlContext: Sealed {
     /// Make the context not current on the calling thread. If you need to
     /// send the context to another thread, use [`Self::make_not_current`]
     /// instead.
+    ///
+    /// The context stays usable and could be made current again with
+    /// [`Self::make_current`]. No GL functions must be called on the calling
+    /// thread while it's not current.
+    #[doc(alias = "unbind")]
     fn make_not_current_in_place(&self) -> Result<()>;
 
     /// Make context current on the calling thread. The `surface` is used as a
@@ -124,9 +173,14 @@ pub trait PossiblyCurrentGlContext: Sealed {
     /// The same as [`Self::make_current`], but provides a way to set draw and
     /// read surfaces explicitly.
     ///
+    /// The support could be checked up front with
+    /// [`DisplayFeatures::SEPARATE_READ_SURFACE`].
+    ///
     /// # Api specific
     ///
     /// - **CGL/WGL:** not supported.
+    ///
+    /// [`DisplayFeatures::SEPARATE_READ_SURFACE`]: crate::display::DisplayFeatures::SEPARATE_READ_SURFACE
     fn make_current_draw_read<T: SurfaceTypeTrait>(
         &self,
         surface_draw: &Self::Surface<T>,
@@ -167,6 +221,9 @@ impl ContextAttributesBuilder {
     /// This option is ignored when using [`Robustness::NoError`].
     ///
     /// The default value for this flag is `false`.
+    ///
+    /// To receive the debug messages use
+    /// [`PossiblyCurrentContext::enable_gl_debug_output`].
     pub fn with_debug(mut self, debug: bool) -> Self {
         self.attributes.debug = debug;
         self
@@ -177,6 +234,13 @@ impl ContextAttributesBuilder {
     /// To get sharing working it's recommended to use the same [`Config`] when
     /// creating contexts that are going to be shared.
     ///
+    /// The context could belong to a different [`Display`] as long as it's
+    /// compatible with the one creating the new context, which could be
+    /// checked with [`Display::are_compatible`]. On EGL the context creation
+    /// fails with [`ErrorKind::BadContext`] when the shared context doesn't
+    /// belong to the same `EGLDisplay` and with [`ErrorKind::BadMatch`] when
+    /// it uses a different client Api.
+    ///
     /// # Platform specific
     ///
     /// - **Wayland:** both contexts must use the same Wayland connection.
@@ -202,6 +266,13 @@ impl ContextAttributesBuilder {
     /// [`ReleaseBehavior`].
     ///
     /// The default is [`ReleaseBehavior::Flush`].
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL/CGL:** not supported, the other behaviors are only rejected in
+    ///   the [strict mode].
+    ///
+    /// [strict mode]: Self::with_strict
     pub fn with_release_behavior(mut self, release_behavior: ReleaseBehavior) -> Self {
         self.attributes.release_behavior = release_behavior;
         self
@@ -213,7 +284,12 @@ impl ContextAttributesBuilder {
     ///
     /// # Api specific
     ///
+    /// - **EGL:** requires EGL 1.5 or `EGL_KHR_create_context`, otherwise the
+    ///   driver picks the profile on its own, or the creation fails with
+    ///   [`ErrorKind::NotSupported`] in the [strict mode].
     /// - **macOS:** not supported, the latest is picked automatically.
+    ///
+    /// [strict mode]: Self::with_strict
     pub fn with_profile(mut self, profile: GlProfile) -> Self {
         self.attributes.profile = Some(profile);
         self
@@ -227,6 +303,25 @@ impl ContextAttributesBuilder {
         self
     }
 
+    /// Allow the context to have a lower version than the one requested with
+    /// [`ContextApi::Gles`].
+    ///
+    /// By default the creation of the OpenGL ES context fails with
+    /// [`ErrorKind::BadContext`] when the achieved major version is lower than
+    /// the requested one, since such context is likely to fail compiling the
+    /// shaders written for the requested GLSL ES version.
+    ///
+    /// # Api specific
+    ///
+    /// - **GLX/WGL:** the version is always honored by the context creation.
+    /// - **CGL:** not supported.
+    ///
+    /// [`ErrorKind::BadContext`]: crate::error::ErrorKind::BadContext
+    pub fn with_version_downgrade(mut self, allow: bool) -> Self {
+        self.attributes.allow_version_downgrade = allow;
+        self
+    }
+
     /// Set the priority hint, which might not be honored if the API does not
     /// support it, if there are constraints on the number of high priority
     /// contexts available in the system, or system policy limits access to
@@ -245,9 +340,102 @@ impl ContextAttributesBuilder {
         self
     }
 
+    /// Select the virtual screen, thus the renderer, the context should use.
+    ///
+    /// On the systems with multiple GPUs this could be used to pin the
+    /// rendering to the particular GPU. The virtual screens are enumerated by
+    /// the [`Config`].
+    ///
+    /// By default the virtual screen is picked by the system.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL/GLX/WGL:** not supported.
+    ///
+    /// [`Config`]: crate::config::Config
+    pub fn with_virtual_screen(mut self, virtual_screen: u32) -> Self {
+        self.attributes.virtual_screen = Some(virtual_screen);
+        self
+    }
+
+    /// Make calling the functions removed from the core profile crash the
+    /// process instead of generating the GL error.
+    ///
+    /// This is useful to catch the use of the legacy functions during the
+    /// development.
+    ///
+    /// The default value for this flag is `false`.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL/GLX/WGL:** not supported.
+    pub fn with_crash_on_removed_functions(mut self, crash: bool) -> Self {
+        self.attributes.crash_on_removed_functions = crash;
+        self
+    }
+
+    /// Fail the context creation when some of the requested attributes can't
+    /// be honored instead of silently ignoring them.
+    ///
+    /// By default the attributes which are only hints, like the
+    /// [`Priority`] or the debug flag, are dropped when the platform doesn't
+    /// support them. The same goes for the
+    /// [`Self::with_default_swap_interval`], which failure to apply fails
+    /// making the context current in the strict mode. The strict mode is meant
+    /// for the CI and the applications relying on them for correctness.
+    ///
+    /// The default value for this flag is `false`.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.attributes.strict = strict;
+        self
+    }
+
+    /// Request the protected context, which is required to render into the
+    /// protected surfaces created with
+    /// [`SurfaceAttributesBuilder::with_protected`].
+    ///
+    /// The context creation fails when the protected content isn't
+    /// supported.
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** requires `EGL_EXT_protected_content`.
+    /// - **GLX/WGL/CGL:** not supported.
+    ///
+    /// [`SurfaceAttributesBuilder::with_protected`]: crate::surface::SurfaceAttributesBuilder::with_protected
+    pub fn with_protected(mut self, protected: bool) -> Self {
+        self.attributes.protected = protected;
+        self
+    }
+
+    /// Set the swap interval the window surfaces inherit from the context.
+    ///
+    /// The interval is applied to the [`WindowSurface`] when it's made
+    /// current with the context for the first time, unless it was already
+    /// set with [`GlSurface::set_swap_interval`], which could still be used
+    /// to override it per surface afterwards. The failure to apply it, e.g.
+    /// when the platform doesn't support the requested interval, is ignored,
+    /// like with the rest of the hints, unless the context is created in the
+    /// [strict mode], where making it current fails instead.
+    ///
+    /// The interval is only applied by the cross-platform
+    /// [`NotCurrentContext`] and [`PossiblyCurrentContext`], the contexts of
+    /// the particular api ignore it.
+    ///
+    /// By default the swap interval of the surfaces is left to the platform.
+    ///
+    /// [strict mode]: Self::with_strict
+    pub fn with_default_swap_interval(mut self, interval: SwapInterval) -> Self {
+        self.attributes.default_swap_interval = Some(interval);
+        self
+    }
+
     /// Build the context attributes.
     ///
     /// The `raw_window_handle` isn't required and here for WGL compatibility.
+    /// The attributes aren't validated, use [`Self::try_build`] for that.
     ///
     /// # Api specific
     ///
@@ -257,6 +445,63 @@ impl ContextAttributesBuilder {
         self.attributes.raw_window_handle = raw_window_handle;
         self.attributes
     }
+
+    /// Build the context attributes, validating that the requested api,
+    /// version, profile, and flags could be used together.
+    ///
+    /// The conflicting attributes are reported with
+    /// [`ErrorKind::BadApiUsage`] describing the conflict, instead of leaving
+    /// them to the driver, which would either fail the context creation or
+    /// ignore some of them. The attributes not supported by the particular
+    /// platform are still checked when creating the context.
+    ///
+    /// ```
+    /// use glutin::context::{ContextApi, ContextAttributesBuilder, GlProfile, Robustness, Version};
+    /// use glutin::error::ErrorKind;
+    ///
+    /// let gl = |major, minor| ContextApi::OpenGl(Some(Version::new(major, minor)));
+    /// let gles = ContextApi::Gles(None);
+    /// let builder = ContextAttributesBuilder::new;
+    ///
+    /// let valid = [
+    ///     builder(),
+    ///     builder().with_context_api(gl(2, 1)),
+    ///     builder().with_context_api(gles).with_debug(true),
+    ///     builder().with_profile(GlProfile::Core),
+    ///     builder().with_context_api(gl(3, 2)).with_profile(GlProfile::Core),
+    ///     builder().with_context_api(gl(4, 6)).with_profile(GlProfile::Compatibility),
+    ///     builder().with_robustness(Robustness::NoError),
+    ///     builder().with_robustness(Robustness::RobustLoseContextOnReset).with_debug(true),
+    ///     builder().with_crash_on_removed_functions(true),
+    ///     builder().with_profile(GlProfile::Core).with_crash_on_removed_functions(true),
+    /// ];
+    /// for attributes in valid {
+    ///     assert!(attributes.try_build(None).is_ok());
+    /// }
+    ///
+    /// let invalid = [
+    ///     builder().with_context_api(gles).with_profile(GlProfile::Core),
+    ///     builder().with_context_api(gl(3, 1)).with_profile(GlProfile::Core),
+    ///     builder().with_context_api(gl(2, 1)).with_profile(GlProfile::Compatibility),
+    ///     builder().with_robustness(Robustness::NoError).with_debug(true),
+    ///     builder().with_context_api(gles).with_crash_on_removed_functions(true),
+    ///     builder().with_profile(GlProfile::Compatibility).with_crash_on_removed_functions(true),
+    /// ];
+    /// for attributes in invalid {
+    ///     let error = attributes.try_build(None).unwrap_err();
+    ///     assert!(matches!(error.error_kind(), ErrorKind::BadApiUsage(_)));
+    /// }
+    /// ```
+    ///
+    /// [`ErrorKind::BadApiUsage`]: crate::error::ErrorKind::BadApiUsage
+    pub fn try_build(
+        self,
+        raw_window_handle: Option<RawWindowHandle>,
+    ) -> Result<ContextAttributes> {
+        let attributes = self.build(raw_window_handle);
+        attributes.validate()?;
+        Ok(attributes)
+    }
 }
 
 /// The attributes that are used to create a graphics context.
@@ -272,13 +517,130 @@ pub struct ContextAttributes {
 
     pub(crate) api: Option<ContextApi>,
 
+    pub(crate) allow_version_downgrade: bool,
+
     pub(crate) priority: Option<Priority>,
 
+    pub(crate) crash_on_removed_functions: bool,
+
+    pub(crate) virtual_screen: Option<u32>,
+
+    pub(crate) strict: bool,
+
+    pub(crate) protected: bool,
+
     pub(crate) shared_context: Option<RawContext>,
 
+    pub(crate) default_swap_interval: Option<SwapInterval>,
+
     pub(crate) raw_window_handle: Option<RawWindowHandle>,
 }
 
+/// The [`ContextAttributes`] the context was created with, without the raw
+/// handles.
+#[derive(Debug, Clone)]
+pub(crate) struct CreationAttributes(ContextAttributes);
+
+impl CreationAttributes {
+    pub(crate) fn new(attributes: &ContextAttributes) -> Self {
+        let mut attributes = attributes.clone();
+        attributes.shared_context = None;
+        attributes.raw_window_handle = None;
+        Self(attributes)
+    }
+}
+
+impl Deref for CreationAttributes {
+    type Target = ContextAttributes;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// SAFETY: the raw handles, which are the only non thread safe attributes, are
+// stripped.
+unsafe impl Send for CreationAttributes {}
+unsafe impl Sync for CreationAttributes {}
+
+impl ContextAttributes {
+    /// Check that the attributes don't conflict with each other.
+    fn validate(&self) -> Result<()> {
+        let is_gles = matches!(self.api, Some(ContextApi::Gles(_)));
+
+        if let Some(profile) = self.profile {
+            if is_gles {
+                return Err(ErrorKind::BadApiUsage("profile requires the OpenGL api").into());
+            }
+
+            if let Some(ContextApi::OpenGl(Some(version))) = self.api {
+                if version < Version::new(3, 2) {
+                    return Err(
+                        ErrorKind::BadApiUsage("profile requires OpenGL 3.2 or greater").into()
+                    );
+                }
+            }
+
+            if profile == GlProfile::Compatibility && self.crash_on_removed_functions {
+                return Err(ErrorKind::BadApiUsage(
+                    "crashing on removed functions conflicts with the compatibility profile",
+                )
+                .into());
+            }
+        }
+
+        if self.debug && self.robustness == Robustness::NoError {
+            return Err(ErrorKind::BadApiUsage(
+                "debug flag conflicts with the no error robustness",
+            )
+            .into());
+        }
+
+        if is_gles && self.crash_on_removed_functions {
+            return Err(ErrorKind::BadApiUsage(
+                "crashing on removed functions requires the OpenGL api",
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Report the attribute which can't be honored, failing only in the
+    /// strict mode.
+    pub(crate) fn ignore_attribute(&self, message: &'static str) -> Result<()> {
+        if self.strict {
+            Err(ErrorKind::NotSupported(message).into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Create the context with `create`, retrying without robustness when the
+    /// `TryRobust*` [`Robustness`] isn't supported or rejected as the bad
+    /// attribute. Any other error is returned right away.
+    pub(crate) fn with_robustness_fallback<C>(
+        &self,
+        create: impl Fn(&ContextAttributes) -> Result<C>,
+    ) -> Result<C> {
+        let robustness = match self.robustness {
+            Robustness::TryRobustNoResetNotification => Robustness::RobustNoResetNotification,
+            Robustness::TryRobustLoseContextOnReset => Robustness::RobustLoseContextOnReset,
+            _ => return create(self),
+        };
+
+        let mut attributes = self.clone();
+        attributes.robustness = robustness;
+        match create(&attributes) {
+            Err(err) if err.not_supported() || err.error_kind() == ErrorKind::BadAttribute => {
+                attributes.robustness = Robustness::NotRobust;
+                create(&attributes).map_err(|fallback| fallback.with_source(err))
+            },
+            result => result,
+        }
+    }
+}
+
 /// Specifies the tolerance of the OpenGL context to faults. If you accept
 /// raw OpenGL commands and/or raw shader code from an untrusted source, you
 /// should definitely care about this.
@@ -307,6 +669,18 @@ pub enum Robustness {
     /// context will enter a "context lost" state. It must then be
     /// recreated.
     RobustLoseContextOnReset,
+
+    /// Same as [`Robustness::RobustNoResetNotification`], but the context is
+    /// created without robustness when the robust one couldn't be created.
+    ///
+    /// Use [`GlContext::robustness`] to check the robustness obtained.
+    TryRobustNoResetNotification,
+
+    /// Same as [`Robustness::RobustLoseContextOnReset`], but the context is
+    /// created without robustness when the robust one couldn't be created.
+    ///
+    /// Use [`GlContext::robustness`] to check the robustness obtained.
+    TryRobustLoseContextOnReset,
 }
 
 /// Describes the requested OpenGL context profiles.
@@ -442,25 +816,29 @@ impl NotCurrentGlContext for NotCurrentContext {
         self,
         surface: &Self::Surface<T>,
     ) -> Result<Self::PossiblyCurrentContext> {
-        match (self, surface) {
+        let context = match (self, surface) {
             #[cfg(egl_backend)]
             (Self::Egl(context), Surface::Egl(surface)) => {
-                Ok(PossiblyCurrentContext::Egl(context.make_current(surface)?))
+                PossiblyCurrentContext::Egl(context.make_current(surface)?)
             },
             #[cfg(glx_backend)]
             (Self::Glx(context), Surface::Glx(surface)) => {
-                Ok(PossiblyCurrentContext::Glx(context.make_current(surface)?))
+                PossiblyCurrentContext::Glx(context.make_current(surface)?)
             },
             #[cfg(wgl_backend)]
             (Self::Wgl(context), Surface::Wgl(surface)) => {
-                Ok(PossiblyCurrentContext::Wgl(context.make_current(surface)?))
+                PossiblyCurrentContext::Wgl(context.make_current(surface)?)
             },
             #[cfg(cgl_backend)]
             (Self::Cgl(context), Surface::Cgl(surface)) => {
-                Ok(PossiblyCurrentContext::Cgl(context.make_current(surface)?))
+                PossiblyCurrentContext::Cgl(context.make_current(surface)?)
             },
             _ => unreachable!(),
-        }
+        };
+
+        surface.apply_default_swap_interval(&context)?;
+
+        Ok(context)
     }
 
     fn make_current_draw_read<T: SurfaceTypeTrait>(
@@ -468,25 +846,29 @@ impl NotCurrentGlContext for NotCurrentContext {
         surface_draw: &Self::Surface<T>,
         surface_read: &Self::Surface<T>,
     ) -> Result<Self::PossiblyCurrentContext> {
-        match (self, surface_draw, surface_read) {
+        let context = match (self, surface_draw, surface_read) {
             #[cfg(egl_backend)]
             (Self::Egl(context), Surface::Egl(draw), Surface::Egl(read)) => {
-                Ok(PossiblyCurrentContext::Egl(context.make_current_draw_read(draw, read)?))
+                PossiblyCurrentContext::Egl(context.make_current_draw_read(draw, read)?)
             },
             #[cfg(glx_backend)]
             (Self::Glx(context), Surface::Glx(draw), Surface::Glx(read)) => {
-                Ok(PossiblyCurrentContext::Glx(context.make_current_draw_read(draw, read)?))
+                PossiblyCurrentContext::Glx(context.make_current_draw_read(draw, read)?)
             },
             #[cfg(wgl_backend)]
             (Self::Wgl(context), Surface::Wgl(draw), Surface::Wgl(read)) => {
-                Ok(PossiblyCurrentContext::Wgl(context.make_current_draw_read(draw, read)?))
+                PossiblyCurrentContext::Wgl(context.make_current_draw_read(draw, read)?)
             },
             #[cfg(cgl_backend)]
             (Self::Cgl(context), Surface::Cgl(draw), Surface::Cgl(read)) => {
-                Ok(PossiblyCurrentContext::Cgl(context.make_current_draw_read(draw, read)?))
+                PossiblyCurrentContext::Cgl(context.make_current_draw_read(draw, read)?)
             },
             _ => unreachable!(),
-        }
+        };
+
+        surface_draw.apply_default_swap_interval(&context)?;
+
+        Ok(context)
     }
 }
 
@@ -498,6 +880,10 @@ impl GlContext for NotCurrentContext {
     fn priority(&self) -> Priority {
         gl_api_dispatch!(self; Self(context) => context.priority())
     }
+
+    fn robustness(&self) -> Robustness {
+        gl_api_dispatch!(self; Self(context) => context.robustness())
+    }
 }
 
 impl GetGlConfig for NotCurrentContext {
@@ -592,7 +978,11 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
             #[cfg(cgl_backend)]
             (Self::Cgl(context), Surface::Cgl(surface)) => context.make_current(surface),
             _ => unreachable!(),
-        }
+        }?;
+
+        surface.apply_default_swap_interval(self)?;
+
+        Ok(())
     }
 
     fn make_current_draw_read<T: SurfaceTypeTrait>(
@@ -618,6 +1008,457 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
                 context.make_current_draw_read(draw, read)
             },
             _ => unreachable!(),
+        }?;
+
+        surface_draw.apply_default_swap_interval(self)?;
+
+        Ok(())
+    }
+}
+
+impl PossiblyCurrentContext {
+    /// The attributes the context was created with.
+    pub(crate) fn context_attributes(&self) -> &ContextAttributes {
+        gl_api_dispatch!(self; Self(context) => context.context_attributes())
+    }
+
+    /// Make each surface current in order and swap its buffers.
+    ///
+    /// The surfaces are presented in the order they were passed, and each of
+    /// them still presents independently, thus a failure to swap one surface
+    /// stops the processing with the surfaces before it being already
+    /// presented. When the surface is already current for drawing the
+    /// make current is skipped to avoid redundant flushes.
+    ///
+    /// When the function returns, the context is current with the last
+    /// processed surface.
+    pub fn swap_all(&self, surfaces: &[&Surface<WindowSurface>]) -> Result<()> {
+        for surface in surfaces {
+            if !surface.is_current_draw(self) {
+                self.make_current(surface)?;
+            }
+
+            surface.swap_buffers(self)?;
+        }
+
+        Ok(())
+    }
+
+    /// Create the context sharing the objects with this one, meant to upload
+    /// the resources, like textures, from the worker thread.
+    ///
+    /// The context is created with the same config and attributes as this
+    /// one, like the api, version, profile, and robustness. The
+    /// returned [`NotCurrentContext`] should be sent to the worker thread and
+    /// made current there with [`NotCurrentGlContext::make_current_surfaceless`]
+    /// or, when it's not supported, with a small pbuffer created by
+    /// [`GlDisplay::create_pbuffer_surface`]. The uploaded objects become
+    /// visible to this context once the worker thread synchronized with it,
+    /// e.g. with the fence or `glFinish`.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** making the context current without surface requires
+    ///   `EGL_KHR_surfaceless_context`.
+    /// - **GLX/WGL:** making the context current without surface requires
+    ///   the context to be created with `create_context` extensions.
+    pub fn create_shared_streaming_context(&self) -> Result<NotCurrentContext> {
+        let mut context_attributes = ContextAttributes::clone(self.context_attributes());
+        context_attributes.shared_context = Some(self.raw_context());
+
+        // SAFETY: no raw window handle is passed.
+        unsafe { self.display().create_context(&self.config(), &context_attributes) }
+    }
+
+    /// Make the context current with the `surface` until the returned guard
+    /// is dropped, restoring the previously current context afterwards.
+    ///
+    /// This is meant for the library code, which must not change the context
+    /// current on the caller's thread. When no context was current before,
+    /// this context is made not current on drop.
+    pub fn make_current_guarded<T: SurfaceTypeTrait>(
+        &self,
+        surface: &Surface<T>,
+    ) -> Result<CurrentContextGuard<'_>> {
+        let state =
+            gl_api_dispatch!(self; Self(context) => context.save_current(); as CurrentState);
+        self.make_current(surface)?;
+        Ok(CurrentContextGuard { context: self, state })
+    }
+
+    /// Route the messages from the `GL_KHR_debug` output into the `callback`.
+    ///
+    /// Only messages with the severity of at least `min_severity` are
+    /// delivered. The output is made synchronous, so the `callback` is called
+    /// from the thread issuing the offending GL command.
+    ///
+    /// The context must be current and should be created with
+    /// [`ContextAttributesBuilder::with_debug`], since non-debug contexts
+    /// may not emit any messages.
+    ///
+    /// The `callback` is kept alive until it's replaced by calling this
+    /// function again or the context is dropped.
+    ///
+    /// ```no_run
+    /// # fn enable(context: &glutin::context::PossiblyCurrentContext) {
+    /// use glutin::context::DebugSeverity;
+    ///
+    /// context
+    ///     .enable_gl_debug_output(DebugSeverity::Medium, |message| {
+    ///         eprintln!("{:?}: {}", message.severity, message.message);
+    ///     })
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn enable_gl_debug_output<F>(&self, min_severity: DebugSeverity, callback: F) -> Result<()>
+    where
+        F: Fn(&DebugMessage<'_>) + Send + Sync + 'static,
+    {
+        if !self.is_current() {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        type GlEnable = unsafe extern "system" fn(u32);
+        type GlDebugMessageCallback = unsafe extern "system" fn(GlDebugProc, *const ffi::c_void);
+
+        let debug_message_callback: GlDebugMessageCallback =
+            [&b"glDebugMessageCallback\0"[..], b"glDebugMessageCallbackKHR\0"]
+                .into_iter()
+                .find_map(|symbol| unsafe { self.load_gl(symbol) })
+                .ok_or(ErrorKind::NotSupported("GL_KHR_debug is not supported"))?;
+        let enable: GlEnable = unsafe { self.load_gl(b"glEnable\0") }
+            .ok_or(ErrorKind::NotSupported("failed to load glEnable"))?;
+
+        let state = Box::new(DebugCallbackState { min_severity, callback: Box::new(callback) });
+        let mut slot = gl_api_dispatch!(self; Self(context) => context.debug_callback())
+            .0
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        unsafe {
+            enable(GL_DEBUG_OUTPUT);
+            enable(GL_DEBUG_OUTPUT_SYNCHRONOUS);
+            debug_message_callback(gl_debug_proc, &*state as *const _ as *const ffi::c_void);
+        }
+
+        // The previous state is no longer referenced by the context, so it's freed.
+        *slot = Some(state);
+
+        Ok(())
+    }
+
+    /// Whether the context is still usable, e.g. after the display
+    /// disconnect.
+    ///
+    /// The check is a lightweight query, so it could be done before
+    /// rendering every frame. To detect the GPU reset use the robust context
+    /// and `glGetGraphicsResetStatus` instead.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL/CGL:** always `true`.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(context) => context.is_valid(),
+            #[cfg(glx_backend)]
+            Self::Glx(context) => context.is_valid(),
+            _ => true,
+        }
+    }
+
+    /// The virtual screen, selecting the renderer, the context is using.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL/GLX/WGL:** always returns `None`.
+    pub fn virtual_screen(&self) -> Option<u32> {
+        match self {
+            #[cfg(cgl_backend)]
+            Self::Cgl(context) => Some(context.virtual_screen()),
+            _ => None,
+        }
+    }
+
+    /// Move the context to the given virtual screen, e.g. to follow the
+    /// display the window is on.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL/GLX/WGL:** not supported.
+    pub fn set_virtual_screen(&self, virtual_screen: u32) -> Result<()> {
+        match self {
+            #[cfg(cgl_backend)]
+            Self::Cgl(context) => {
+                context.set_virtual_screen(virtual_screen);
+                Ok(())
+            },
+            _ => {
+                let _ = virtual_screen;
+                Err(ErrorKind::NotSupported("virtual screens are not supported").into())
+            },
+        }
+    }
+
+    /// Query the information about the GL implementation behind the context
+    /// with `glGetString`.
+    ///
+    /// The context must be current on the calling thread.
+    pub fn info(&self) -> Result<GlInfo> {
+        if !self.is_current() {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        let get_string: GlGetString = unsafe { self.load_gl(b"glGetString\0") }
+            .ok_or(ErrorKind::NotSupported("failed to load glGetString"))?;
+        let string =
+            |name| unsafe { gl_str(get_string(name)) }.map(|string| string.to_string_lossy());
+
+        let extensions = unsafe { self.gl_extensions() }
+            .into_iter()
+            .flatten()
+            .map(|extension| String::from_utf8_lossy(extension).into_owned())
+            .collect();
+
+        Ok(GlInfo {
+            vendor: string(GL_VENDOR).unwrap_or_default().into_owned(),
+            renderer: string(GL_RENDERER).unwrap_or_default().into_owned(),
+            version: string(GL_VERSION).unwrap_or_default().into_owned(),
+            shading_language_version: string(GL_SHADING_LANGUAGE_VERSION).map(Cow::into_owned),
+            extensions,
+        })
+    }
+
+    /// Check whether the current context supports the GL `extension`, without
+    /// loading all the strings like [`Self::info`] does.
+    fn has_gl_extension(&self, extension: &str) -> bool {
+        self.is_current()
+            && unsafe { self.gl_extensions() }
+                .is_some_and(|mut extensions| extensions.any(|name| name == extension.as_bytes()))
+    }
+
+    /// Iterate over the extensions supported by the current context.
+    ///
+    /// `None` is returned when `glGetString` couldn't be loaded.
+    ///
+    /// # Safety
+    ///
+    /// The context must be current on the calling thread, and stay current
+    /// while the returned names are used.
+    unsafe fn gl_extensions(&self) -> Option<Box<dyn Iterator<Item = &[u8]> + '_>> {
+        let get_string: GlGetString = unsafe { self.load_gl(b"glGetString\0")? };
+        let major_version = unsafe { gl_str(get_string(GL_VERSION)) }
+            .map_or(0, |version| gl_major_version(&version.to_string_lossy()));
+
+        // `GL_EXTENSIONS` can't be used with `glGetString` in the core profile.
+        if major_version >= 3 {
+            let get_string_i: Option<GlGetStringi> = unsafe { self.load_gl(b"glGetStringi\0") };
+            let get_integer_v: Option<GlGetIntegerv> = unsafe { self.load_gl(b"glGetIntegerv\0") };
+            if let (Some(get_string_i), Some(get_integer_v)) = (get_string_i, get_integer_v) {
+                let mut num_extensions = 0;
+                unsafe { get_integer_v(GL_NUM_EXTENSIONS, &mut num_extensions) };
+                return Some(Box::new((0..num_extensions.max(0) as u32).filter_map(
+                    move |index| {
+                        unsafe { gl_str(get_string_i(GL_EXTENSIONS, index)) }.map(CStr::to_bytes)
+                    },
+                )));
+            }
+        }
+
+        let extensions = unsafe { gl_str(get_string(GL_EXTENSIONS)) };
+        Some(Box::new(
+            extensions
+                .map(CStr::to_bytes)
+                .unwrap_or_default()
+                .split(|byte| byte.is_ascii_whitespace())
+                .filter(|name| !name.is_empty()),
+        ))
+    }
+
+    /// Load the GL function `symbol`, which must be nul-terminated, as `F`.
+    ///
+    /// # Safety
+    ///
+    /// `F` must be the function pointer type matching the signature of the
+    /// loaded function.
+    unsafe fn load_gl<F: Copy>(&self, symbol: &[u8]) -> Option<F> {
+        assert_eq!(std::mem::size_of::<F>(), std::mem::size_of::<*const ffi::c_void>());
+        let fn_ptr = self.display().get_proc_address(CStr::from_bytes_with_nul(symbol).unwrap());
+        (!fn_ptr.is_null()).then(|| unsafe { std::mem::transmute_copy(&fn_ptr) })
+    }
+
+    /// Query the [`ReleaseBehavior`] the context ended up with, reading
+    /// `GL_CONTEXT_RELEASE_BEHAVIOR`.
+    ///
+    /// This could be used to verify that the behavior requested with
+    /// [`ContextAttributesBuilder::with_release_behavior`] took effect.
+    ///
+    /// The context must be current on the calling thread. When it's not, or
+    /// `GL_KHR_context_flush_control` is not supported, the default
+    /// [`ReleaseBehavior::Flush`] is returned.
+    pub fn release_behavior(&self) -> ReleaseBehavior {
+        if !self.has_gl_extension("GL_KHR_context_flush_control") {
+            return ReleaseBehavior::Flush;
+        }
+
+        let get_integer_v: GlGetIntegerv = match unsafe { self.load_gl(b"glGetIntegerv\0") } {
+            Some(get_integer_v) => get_integer_v,
+            None => return ReleaseBehavior::Flush,
+        };
+
+        let mut behavior = GL_CONTEXT_RELEASE_BEHAVIOR_FLUSH as i32;
+        unsafe { get_integer_v(GL_CONTEXT_RELEASE_BEHAVIOR, &mut behavior) };
+
+        match behavior as u32 {
+            GL_NONE => ReleaseBehavior::None,
+            _ => ReleaseBehavior::Flush,
+        }
+    }
+
+    /// Whether the context is the debug one, i.e. `GL_CONTEXT_FLAG_DEBUG_BIT`
+    /// is set in `GL_CONTEXT_FLAGS`.
+    ///
+    /// Some drivers ignore the [`ContextAttributesBuilder::with_debug`], so
+    /// this could be used to check whether the
+    /// [`PossiblyCurrentContext::enable_gl_debug_output`] will deliver any
+    /// messages.
+    ///
+    /// The context must be current on the calling thread, otherwise `false`
+    /// is returned. The flags are only available since OpenGL 3.0 and OpenGL
+    /// ES 3.2, with the older contexts `false` is returned as well.
+    ///
+    /// ```no_run
+    /// # use glutin::context::{ContextAttributesBuilder, NotCurrentGlContext};
+    /// # use glutin::prelude::*;
+    /// # let display: glutin::display::Display = unimplemented!();
+    /// # let config: glutin::config::Config = unimplemented!();
+    /// # let surface: glutin::surface::Surface<glutin::surface::WindowSurface> = unimplemented!();
+    /// let attributes = ContextAttributesBuilder::new().with_debug(true).build(None);
+    /// let context = unsafe { display.create_context(&config, &attributes).unwrap() };
+    /// let context = context.make_current(&surface).unwrap();
+    /// if !context.is_debug() {
+    ///     println!("the driver ignored the debug flag");
+    /// }
+    /// ```
+    pub fn is_debug(&self) -> bool {
+        if !self.is_current() {
+            return false;
+        }
+
+        let get_integer_v: GlGetIntegerv = match unsafe { self.load_gl(b"glGetIntegerv\0") } {
+            Some(get_integer_v) => get_integer_v,
+            None => return false,
+        };
+
+        let mut flags = 0;
+        unsafe { get_integer_v(GL_CONTEXT_FLAGS, &mut flags) };
+
+        flags as u32 & GL_CONTEXT_FLAG_DEBUG_BIT != 0
+    }
+
+    /// Query the common implementation limits with `glGetIntegerv`, which are
+    /// usually needed to size the render targets.
+    ///
+    /// The limits not supported by the context, like `GL_MAX_SAMPLES` on
+    /// OpenGL ES 2.0, are reported as `0`, in which case the
+    /// `GL_INVALID_ENUM` error is left for the `glGetError`.
+    ///
+    /// The context must be current on the calling thread.
+    pub fn limits(&self) -> Result<GlLimits> {
+        if !self.is_current() {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        let get_integer_v: GlGetIntegerv = unsafe { self.load_gl(b"glGetIntegerv\0") }
+            .ok_or(ErrorKind::NotSupported("failed to load glGetIntegerv"))?;
+
+        unsafe {
+            let integer = |name| {
+                let mut value = 0;
+                get_integer_v(name, &mut value);
+                value.max(0) as u32
+            };
+
+            let mut viewport_dims = [0; 2];
+            get_integer_v(GL_MAX_VIEWPORT_DIMS, viewport_dims.as_mut_ptr());
+
+            Ok(GlLimits {
+                max_texture_size: integer(GL_MAX_TEXTURE_SIZE),
+                max_renderbuffer_size: integer(GL_MAX_RENDERBUFFER_SIZE),
+                max_viewport_dims: (viewport_dims[0].max(0) as u32, viewport_dims[1].max(0) as u32),
+                max_samples: integer(GL_MAX_SAMPLES),
+            })
+        }
+    }
+
+    /// Clear the `surface` to the `color` with `glClearColor` and `glClear`,
+    /// and swap its buffers.
+    ///
+    /// This is meant for the smoke tests checking that the context works
+    /// right after the bootstrapping, without loading the GL functions.
+    ///
+    /// The context must be current with the `surface` on the calling thread.
+    pub fn clear_and_present<T: SurfaceTypeTrait>(
+        &self,
+        surface: &Surface<T>,
+        color: [f32; 4],
+    ) -> Result<()> {
+        if !surface.is_current(self) {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        type GlClearColor = unsafe extern "system" fn(f32, f32, f32, f32);
+        type GlClear = unsafe extern "system" fn(u32);
+
+        let (clear_color, clear): (GlClearColor, GlClear) =
+            match unsafe { (self.load_gl(b"glClearColor\0"), self.load_gl(b"glClear\0")) } {
+                (Some(clear_color), Some(clear)) => (clear_color, clear),
+                _ => return Err(ErrorKind::NotSupported("failed to load glClear").into()),
+            };
+
+        unsafe {
+            let [red, green, blue, alpha] = color;
+            clear_color(red, green, blue, alpha);
+            clear(GL_COLOR_BUFFER_BIT);
+        }
+
+        surface.swap_buffers(self)
+    }
+
+    /// Block until all the previously submitted GL commands are complete,
+    /// calling `glFinish`.
+    ///
+    /// The context must be current on the calling thread.
+    pub fn finish(&self) -> Result<()> {
+        type GlFinish = unsafe extern "system" fn();
+
+        let finish: GlFinish = unsafe { self.load_gl(b"glFinish\0") }
+            .ok_or(ErrorKind::NotSupported("glFinish is not available"))?;
+        unsafe { finish() };
+
+        Ok(())
+    }
+
+    /// Insert the [`Fence`] signaled once all the previously submitted GL
+    /// commands are complete, so the wait could be deferred.
+    ///
+    /// When the fences are not supported, this falls back to
+    /// [`PossiblyCurrentContext::finish`] and returns the already signaled
+    /// [`Fence::Finished`].
+    ///
+    /// The context must be current on the calling thread.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** the fence requires `EGL_KHR_fence_sync`.
+    /// - **GLX/WGL/CGL:** always falls back to `glFinish`.
+    pub fn finish_fenced(&self) -> Result<Fence> {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(context) if context.display().extensions().contains("EGL_KHR_fence_sync") => {
+                Ok(Fence::Egl(context.display().create_fence()?))
+            },
+            _ => self.finish().map(|_| Fence::Finished),
         }
     }
 }
@@ -630,6 +1471,10 @@ impl GlContext for PossiblyCurrentContext {
     fn priority(&self) -> Priority {
         gl_api_dispatch!(self; Self(context) => context.priority())
     }
+
+    fn robustness(&self) -> Robustness {
+        gl_api_dispatch!(self; Self(context) => context.robustness())
+    }
 }
 
 impl GetGlConfig for PossiblyCurrentContext {
@@ -656,6 +1501,53 @@ impl AsRawContext for PossiblyCurrentContext {
 
 impl Sealed for PossiblyCurrentContext {}
 
+/// The guard restoring the previously current context on drop.
+///
+/// It's created with [`PossiblyCurrentContext::make_current_guarded`]. The
+/// errors happening when restoring the context are ignored.
+#[derive(Debug)]
+pub struct CurrentContextGuard<'a> {
+    context: &'a PossiblyCurrentContext,
+    state: CurrentState,
+}
+
+impl Drop for CurrentContextGuard<'_> {
+    fn drop(&mut self) {
+        let _ = match (self.context, &self.state) {
+            #[cfg(egl_backend)]
+            (PossiblyCurrentContext::Egl(context), CurrentState::Egl(state)) => {
+                context.restore_current(state)
+            },
+            #[cfg(glx_backend)]
+            (PossiblyCurrentContext::Glx(context), CurrentState::Glx(state)) => {
+                context.restore_current(state)
+            },
+            #[cfg(wgl_backend)]
+            (PossiblyCurrentContext::Wgl(context), CurrentState::Wgl(state)) => {
+                context.restore_current(state)
+            },
+            #[cfg(cgl_backend)]
+            (PossiblyCurrentContext::Cgl(context), CurrentState::Cgl(state)) => {
+                context.restore_current(state)
+            },
+            _ => unreachable!(),
+        };
+    }
+}
+
+/// The context current on the thread before making the other one current.
+#[derive(Debug)]
+enum CurrentState {
+    #[cfg(egl_backend)]
+    Egl(crate::api::egl::context::CurrentState),
+    #[cfg(glx_backend)]
+    Glx(crate::api::glx::context::CurrentState),
+    #[cfg(wgl_backend)]
+    Wgl(crate::api::wgl::context::CurrentState),
+    #[cfg(cgl_backend)]
+    Cgl(crate::api::cgl::context::CurrentState),
+}
+
 /// Raw context.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RawContext {
@@ -695,6 +1587,243 @@ pub enum Priority {
     Realtime,
 }
 
+/// The severity of the [`DebugMessage`].
+///
+/// The variants are ordered from the least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DebugSeverity {
+    /// `GL_DEBUG_SEVERITY_NOTIFICATION`, anything that isn't an error or
+    /// performance issue.
+    Notification,
+    /// `GL_DEBUG_SEVERITY_LOW`, redundant state changes or unimportant
+    /// undefined behavior.
+    Low,
+    /// `GL_DEBUG_SEVERITY_MEDIUM`, major performance warnings or usage of
+    /// deprecated functionality.
+    Medium,
+    /// `GL_DEBUG_SEVERITY_HIGH`, errors or dangerous undefined behavior.
+    High,
+}
+
+impl DebugSeverity {
+    fn from_raw(severity: u32) -> Option<Self> {
+        match severity {
+            0x826b => Some(Self::Notification),
+            0x9148 => Some(Self::Low),
+            0x9147 => Some(Self::Medium),
+            0x9146 => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
+/// The implementation limits returned by the
+/// [`PossiblyCurrentContext::limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlLimits {
+    /// The `GL_MAX_TEXTURE_SIZE`, the largest width and height of the 2D
+    /// texture.
+    pub max_texture_size: u32,
+
+    /// The `GL_MAX_RENDERBUFFER_SIZE`, the largest width and height of the
+    /// renderbuffer.
+    pub max_renderbuffer_size: u32,
+
+    /// The `GL_MAX_VIEWPORT_DIMS`, the largest `(width, height)` of the
+    /// viewport.
+    pub max_viewport_dims: (u32, u32),
+
+    /// The `GL_MAX_SAMPLES`, the largest number of samples of the
+    /// multisampled renderbuffer.
+    pub max_samples: u32,
+}
+
+/// The fence returned by [`PossiblyCurrentContext::finish_fenced`].
+#[derive(Debug)]
+pub enum Fence {
+    /// The EGL fence.
+    #[cfg(egl_backend)]
+    Egl(EglFence),
+
+    /// The commands were already completed with `glFinish`.
+    Finished,
+}
+
+impl Fence {
+    /// Block until the fence is signaled or the `timeout` expires.
+    ///
+    /// Returns `true` when the fence was signaled and `false` on timeout.
+    /// Passing `None` waits without the timeout.
+    pub fn wait(&self, timeout: Option<std::time::Duration>) -> Result<bool> {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(fence) => fence.wait(timeout),
+            Self::Finished => {
+                let _ = timeout;
+                Ok(true)
+            },
+        }
+    }
+}
+
+/// The information about the GL implementation returned by the
+/// [`PossiblyCurrentContext::info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlInfo {
+    /// The `GL_VENDOR` string.
+    pub vendor: String,
+
+    /// The `GL_RENDERER` string, usually naming the GPU.
+    pub renderer: String,
+
+    /// The `GL_VERSION` string.
+    pub version: String,
+
+    /// The `GL_SHADING_LANGUAGE_VERSION` string, which is absent on the
+    /// fixed function implementations.
+    pub shading_language_version: Option<String>,
+
+    /// The supported GL extensions.
+    pub extensions: Vec<String>,
+}
+
+/// The message delivered by the [`PossiblyCurrentContext::enable_gl_debug_output`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugMessage<'a> {
+    /// The raw `GL_DEBUG_SOURCE_*` value of the message.
+    pub source: u32,
+    /// The raw `GL_DEBUG_TYPE_*` value of the message.
+    pub ty: u32,
+    /// The implementation specific id of the message.
+    pub id: u32,
+    /// The severity of the message.
+    pub severity: DebugSeverity,
+    /// The text of the message.
+    pub message: &'a str,
+}
+
+impl DebugMessage<'_> {
+    /// Log the message with the [`log`] crate.
+    ///
+    /// The [`DebugSeverity`] is mapped to the [`log::Level`] from
+    /// [`log::Level::Error`] for [`DebugSeverity::High`] down to
+    /// [`log::Level::Debug`] for [`DebugSeverity::Notification`].
+    #[cfg(feature = "log")]
+    pub fn log(&self) {
+        let level = match self.severity {
+            DebugSeverity::High => log::Level::Error,
+            DebugSeverity::Medium => log::Level::Warn,
+            DebugSeverity::Low => log::Level::Info,
+            DebugSeverity::Notification => log::Level::Debug,
+        };
+
+        log::log!(
+            target: "glutin::gl_debug",
+            level,
+            "[source: {:#x}, type: {:#x}, id: {}] {}",
+            self.source,
+            self.ty,
+            self.id,
+            self.message
+        );
+    }
+}
+
+const GL_VENDOR: u32 = 0x1f00;
+const GL_RENDERER: u32 = 0x1f01;
+const GL_VERSION: u32 = 0x1f02;
+const GL_EXTENSIONS: u32 = 0x1f03;
+const GL_SHADING_LANGUAGE_VERSION: u32 = 0x8b8c;
+const GL_NUM_EXTENSIONS: u32 = 0x821d;
+const GL_NONE: u32 = 0;
+const GL_CONTEXT_RELEASE_BEHAVIOR: u32 = 0x82fb;
+const GL_CONTEXT_RELEASE_BEHAVIOR_FLUSH: u32 = 0x82fc;
+const GL_CONTEXT_FLAGS: u32 = 0x821e;
+const GL_CONTEXT_FLAG_DEBUG_BIT: u32 = 0x2;
+const GL_MAX_TEXTURE_SIZE: u32 = 0x0d33;
+const GL_MAX_VIEWPORT_DIMS: u32 = 0x0d3a;
+const GL_MAX_RENDERBUFFER_SIZE: u32 = 0x84e8;
+const GL_MAX_SAMPLES: u32 = 0x8d57;
+const GL_COLOR_BUFFER_BIT: u32 = 0x4000;
+
+/// Parse the major version from the `GL_VERSION` string, which is prefixed
+/// with `OpenGL ES` on GLES.
+fn gl_major_version(version: &str) -> u32 {
+    version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .and_then(|major| major.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Convert the string returned by `glGetString` or `glGetStringi`.
+///
+/// # Safety
+///
+/// The `string` must be either null or the nul-terminated string, valid for
+/// `'a`.
+unsafe fn gl_str<'a>(string: *const u8) -> Option<&'a CStr> {
+    (!string.is_null()).then(|| unsafe { CStr::from_ptr(string.cast()) })
+}
+
+type GlGetString = unsafe extern "system" fn(u32) -> *const u8;
+type GlGetStringi = unsafe extern "system" fn(u32, u32) -> *const u8;
+type GlGetIntegerv = unsafe extern "system" fn(u32, *mut i32);
+
+const GL_DEBUG_OUTPUT: u32 = 0x92e0;
+const GL_DEBUG_OUTPUT_SYNCHRONOUS: u32 = 0x8242;
+
+type GlDebugProc =
+    extern "system" fn(u32, u32, u32, u32, i32, *const ffi::c_char, *mut ffi::c_void);
+
+/// The state passed as `userParam` to the [`gl_debug_proc`].
+struct DebugCallbackState {
+    min_severity: DebugSeverity,
+    callback: Box<dyn Fn(&DebugMessage<'_>) + Send + Sync>,
+}
+
+/// The [`PossiblyCurrentContext::enable_gl_debug_output`] state owned by the
+/// context, so it lives as long as the context could call it.
+#[derive(Default)]
+pub(crate) struct DebugCallbackSlot(Mutex<Option<Box<DebugCallbackState>>>);
+
+impl fmt::Debug for DebugCallbackSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugCallbackSlot").finish_non_exhaustive()
+    }
+}
+
+extern "system" fn gl_debug_proc(
+    source: u32,
+    ty: u32,
+    id: u32,
+    severity: u32,
+    length: i32,
+    message: *const ffi::c_char,
+    user_param: *mut ffi::c_void,
+) {
+    let state = unsafe { &*(user_param as *const DebugCallbackState) };
+    let severity = match DebugSeverity::from_raw(severity) {
+        Some(severity) if severity >= state.min_severity => severity,
+        _ => return,
+    };
+
+    let message = if message.is_null() {
+        Cow::Borrowed("")
+    } else if length < 0 {
+        unsafe { CStr::from_ptr(message).to_string_lossy() }
+    } else {
+        let message = unsafe { std::slice::from_raw_parts(message.cast(), length as usize) };
+        String::from_utf8_lossy(message)
+    };
+
+    let message = DebugMessage { source, ty, id, severity, message: &message };
+
+    // Unwinding into the GL driver is not allowed.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| (state.callback)(&message)));
+}
+
 /// Pick `GlProfile` and `Version` based on the provided params.
 #[cfg(any(egl_backend, glx_backend, wgl_backend))]
 pub(crate) fn pick_profile(
@@ -2,26 +2,32 @@
 #![allow(unreachable_patterns)]
 
 use std::collections::HashSet;
+use std::env;
 use std::ffi::{self, CStr};
 use std::fmt;
+use std::num::NonZeroU32;
 
 use bitflags::bitflags;
-use raw_window_handle::RawDisplayHandle;
+use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
 
 use crate::config::{Config, ConfigTemplate, GlConfig};
-use crate::context::{ContextAttributes, NotCurrentContext, NotCurrentGlContext};
-use crate::error::Result;
+use crate::context::{
+    ContextAttributes, ContextAttributesBuilder, NotCurrentContext, NotCurrentGlContext,
+    PossiblyCurrentContext, RawContext,
+};
+use crate::error::{NativeError, Result};
 use crate::private::{gl_api_dispatch, Sealed};
 use crate::surface::{
-    GlSurface, PbufferSurface, PixmapSurface, Surface, SurfaceAttributes, WindowSurface,
+    GlSurface, PbufferSurface, PixmapSurface, Surface, SurfaceAttributes, SurfaceAttributesBuilder,
+    SurfaceTypeTrait, WindowSurface,
 };
 
 #[cfg(cgl_backend)]
 use crate::api::cgl::display::Display as CglDisplay;
 #[cfg(egl_backend)]
-use crate::api::egl::display::Display as EglDisplay;
+use crate::api::egl::display::{Display as EglDisplay, EglPlatform};
 #[cfg(glx_backend)]
-use crate::api::glx::display::Display as GlxDisplay;
+use crate::api::glx::display::{Display as GlxDisplay, GlxOptions};
 #[cfg(glx_backend)]
 use crate::api::glx::XlibErrorHookRegistrar;
 #[cfg(wgl_backend)]
@@ -77,6 +83,36 @@ pub trait GlDisplay: Sealed {
         context_attributes: &ContextAttributes,
     ) -> Result<Self::NotCurrentContext>;
 
+    /// Create the graphics platform context with the first supported
+    /// attributes.
+    ///
+    /// The `context_attributes` are tried in order, returning the context
+    /// along with the index of the attributes used to create it. When all of
+    /// them fail, the error of the last attempt is returned with the errors
+    /// of the previous ones available through its [`source`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    ///
+    /// [`source`]: std::error::Error::source
+    unsafe fn create_context_first_supported(
+        &self,
+        config: &Self::Config,
+        context_attributes: &[ContextAttributes],
+    ) -> Result<(Self::NotCurrentContext, usize)>
+    where
+        Self: Sized,
+    {
+        first_success(
+            context_attributes.iter().enumerate(),
+            crate::error::ErrorKind::BadParameter,
+            |(index, context_attributes)| unsafe {
+                Ok((self.create_context(config, context_attributes)?, index))
+            },
+        )
+    }
+
     /// Create the surface that can be used to render into native window.
     ///
     /// # Safety
@@ -104,6 +140,22 @@ pub trait GlDisplay: Sealed {
 
     /// Create the surface that can be used to render into pixmap.
     ///
+    /// The pixmap surface allows offscreen rendering into the X11 pixmap or
+    /// the Windows bitmap, which could be then composited by the non-GL code.
+    /// The size of the surface is the size of the underlying pixmap.
+    ///
+    /// ```no_run
+    /// # use glutin::prelude::*;
+    /// use glutin::surface::{NativePixmap, PixmapSurface, SurfaceAttributesBuilder};
+    /// # let display: glutin::display::Display = unimplemented!();
+    /// # let config: glutin::config::Config = unimplemented!();
+    /// # let xid: std::os::raw::c_ulong = unimplemented!();
+    ///
+    /// let attrs =
+    ///     SurfaceAttributesBuilder::<PixmapSurface>::new().build(NativePixmap::XlibPixmap(xid));
+    /// let surface = unsafe { display.create_pixmap_surface(&config, &attrs).unwrap() };
+    /// ```
+    ///
     /// # Safety
     ///
     /// The [`NativePixmap`] must represent a valid native pixmap.
@@ -124,6 +176,41 @@ pub trait GlDisplay: Sealed {
     ///   loaded.
     fn get_proc_address(&self, addr: &CStr) -> *const ffi::c_void;
 
+    /// Load OpenGL functions in a batch with the given `loader`.
+    ///
+    /// The `loader` is given a function resolving the symbol by its name,
+    /// which could be passed directly to the `load_with` of the bindings
+    /// generated by `gl_generator`. Unlike calling
+    /// [`GlDisplay::get_proc_address`] for each symbol, the buffer for the
+    /// nul-terminated name is reused between the lookups.
+    ///
+    /// ```no_run
+    /// # use glutin::prelude::*;
+    /// # fn load_with<F: FnMut(&'static str) -> *const std::ffi::c_void>(_: F) {}
+    /// # let display: glutin::display::Display = unimplemented!();
+    /// display.load_symbols(|loader| load_with(loader));
+    /// ```
+    ///
+    /// # Api-specific
+    ///
+    /// The same as for [`GlDisplay::get_proc_address`].
+    fn load_symbols<R, F>(&self, loader: F) -> R
+    where
+        Self: Sized,
+        F: FnOnce(&mut dyn FnMut(&str) -> *const ffi::c_void) -> R,
+    {
+        let mut name = Vec::new();
+        loader(&mut |symbol| {
+            name.clear();
+            name.extend_from_slice(symbol.as_bytes());
+            name.push(0);
+            match CStr::from_bytes_with_nul(&name) {
+                Ok(symbol) => self.get_proc_address(symbol),
+                Err(_) => std::ptr::null(),
+            }
+        })
+    }
+
     /// Helper to obtain the information about the underlying display.
     ///
     /// This function is intended to be used for logging purposes to help with
@@ -138,6 +225,10 @@ pub trait GlDisplay: Sealed {
 }
 
 /// Get the [`Display`].
+///
+/// This is implemented by configs, contexts, and surfaces, so the display
+/// could be obtained from any of them instead of storing it separately. The
+/// returned display is a handle, which is cheap to clone.
 pub trait GetGlDisplay: Sealed {
     /// The display used by the object.
     type Target: GlDisplay;
@@ -194,6 +285,310 @@ pub enum Display {
 }
 
 impl Display {
+    /// Check whether both displays use the same Api and the same underlying
+    /// platform display, which is required to share the contexts with
+    /// [`ContextAttributesBuilder::with_sharing`].
+    ///
+    /// That doesn't account for the configs and the client Apis of the
+    /// contexts, which must be compatible as well, thus the context creation
+    /// could still fail with the compatible displays.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** the displays must refer to the same `EGLDisplay`, which is
+    ///   the case when they were created for the same native display.
+    /// - **GLX:** the displays must use the same X11 connection.
+    ///
+    /// [`ContextAttributesBuilder::with_sharing`]: crate::context::ContextAttributesBuilder::with_sharing
+    pub fn are_compatible(&self, other: &Display) -> bool {
+        self.raw_display() == other.raw_display()
+    }
+
+    /// The last raw error reported by the display's Api on the current
+    /// thread, e.g. the value of `eglGetError`.
+    ///
+    /// Unlike the returned [`Error`] this preserves the name of the Api along
+    /// with the raw code, so it could be attached to the bug reports.
+    ///
+    /// [`Error`]: crate::error::Error
+    pub fn last_error(&self) -> Option<NativeError> {
+        let api = match self {
+            #[cfg(egl_backend)]
+            Self::Egl(_) => "EGL",
+            #[cfg(glx_backend)]
+            Self::Glx(_) => "GLX",
+            #[cfg(wgl_backend)]
+            Self::Wgl(_) => "WGL",
+            #[cfg(cgl_backend)]
+            Self::Cgl(_) => "CGL",
+        };
+
+        crate::error::last_native_error(api)
+    }
+
+    /// Check whether the GPU device backing the display was reset, e.g. due
+    /// to the driver timeout (TDR) on Windows.
+    ///
+    /// When the device was reset, the display and all the objects created
+    /// from it must be recreated.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** only ANGLE with the D3D11 renderer on Windows is supported,
+    ///   requires `EGL_ANGLE_device_d3d`.
+    /// - **GLX/WGL/CGL:** always [`DeviceResetStatus::Unknown`].
+    pub fn check_device_reset(&self) -> DeviceResetStatus {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(display) => display.check_device_reset(),
+            _ => DeviceResetStatus::Unknown,
+        }
+    }
+
+    /// Create the context and make it current with the `surface` right away.
+    ///
+    /// When making the context current fails, the context is destroyed. Use
+    /// [`GlDisplay::create_context`] when the context should be sent to
+    /// another thread before making it current.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    pub unsafe fn create_current_context<T: SurfaceTypeTrait>(
+        &self,
+        config: &Config,
+        context_attributes: &ContextAttributes,
+        surface: &Surface<T>,
+    ) -> Result<PossiblyCurrentContext> {
+        let context = unsafe { self.create_context(config, context_attributes)? };
+        context.make_current(surface)
+    }
+
+    /// Create the context sharing the objects with the foreign context, e.g.
+    /// created by the host application with other library, which is only
+    /// available as the [`RawContext`].
+    ///
+    /// The `share_context` overrides the one set with
+    /// [`ContextAttributesBuilder::with_sharing`] in the
+    /// `context_attributes`. It must be of the same api as the display, the
+    /// context creation fails with [`ErrorKind::NotSupported`] otherwise.
+    ///
+    /// # Api-specific
+    ///
+    /// - **CGL:** the `share_context` is the `NSOpenGLContext`, not the
+    ///   `CGLContextObj`.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`]. In addition, the
+    /// `share_context` must point to a valid context created on the display
+    /// compatible with this one, and it must outlive the created context.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
+    pub unsafe fn create_context_sharing_raw(
+        &self,
+        config: &Config,
+        context_attributes: &ContextAttributes,
+        share_context: RawContext,
+    ) -> Result<NotCurrentContext> {
+        let mut context_attributes = context_attributes.clone();
+        context_attributes.shared_context = Some(share_context);
+        unsafe { self.create_context(config, &context_attributes) }
+    }
+
+    /// Create the context for the offscreen rendering along with the `1x1`
+    /// pbuffer to make it current with.
+    ///
+    /// The rendering should happen into the framebuffer objects, the pbuffer
+    /// is only there to satisfy the platforms which can't make the context
+    /// current without the surface. The `config` must support
+    /// [`ConfigSurfaceTypes::PBUFFER`].
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** with the EGL display, e.g. ANGLE, the EGL pbuffer is
+    ///   used, with the WGL display `WGL_ARB_pbuffer` is required.
+    /// - **macOS:** not supported, use
+    ///   [`NotCurrentGlContext::make_current_surfaceless`] instead.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    ///
+    /// [`ConfigSurfaceTypes::PBUFFER`]: crate::config::ConfigSurfaceTypes::PBUFFER
+    /// [`NotCurrentGlContext::make_current_surfaceless`]: crate::context::NotCurrentGlContext::make_current_surfaceless
+    pub unsafe fn create_headless_context(
+        &self,
+        config: &Config,
+        context_attributes: &ContextAttributes,
+    ) -> Result<(NotCurrentContext, Surface<PbufferSurface>)> {
+        let size = NonZeroU32::new(1).unwrap();
+        let surface_attributes =
+            SurfaceAttributesBuilder::<PbufferSurface>::new().build(size, size);
+        let surface = unsafe { self.create_pbuffer_surface(config, &surface_attributes)? };
+        let context = unsafe { self.create_context(config, context_attributes)? };
+        Ok((context, surface))
+    }
+
+    /// Create the context with the default attributes, made current with the
+    /// `1x1` pbuffer, for the throwaway probing, like querying
+    /// `glGetString` or validating the shaders.
+    ///
+    /// The context is current on the calling thread until it's dropped. The
+    /// pbuffer must be kept alive while the context is current with it.
+    ///
+    /// # Platform-specific
+    ///
+    /// The same as for [`Display::create_headless_context`].
+    pub fn create_probe_context(
+        &self,
+        config: &Config,
+    ) -> Result<(PossiblyCurrentContext, Surface<PbufferSurface>)> {
+        let context_attributes = ContextAttributesBuilder::new().build(None);
+        // SAFETY: the default attributes don't carry the raw window handle.
+        let (context, surface) =
+            unsafe { self.create_headless_context(config, &context_attributes)? };
+        let context = context.make_current(&surface)?;
+        Ok((context, surface))
+    }
+
+    /// Find configurations matching the given `template`, which could be used
+    /// to create the surface for the already created window.
+    ///
+    /// The `raw_window_handle` is also set as the
+    /// [`ConfigTemplateBuilder::compatible_with_native_window`].
+    ///
+    /// # Platform-specific
+    ///
+    /// - **X11:** only the configs with the same visual as the window are
+    ///   returned, since others result in `BadMatch` when creating the
+    ///   surface. The filtering is skipped when the handle doesn't have the
+    ///   visual id.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::find_configs`].
+    ///
+    /// [`ConfigTemplateBuilder::compatible_with_native_window`]: crate::config::ConfigTemplateBuilder::compatible_with_native_window
+    pub unsafe fn find_configs_for_window(
+        &self,
+        mut template: ConfigTemplate,
+        raw_window_handle: RawWindowHandle,
+    ) -> Result<Box<dyn Iterator<Item = Config> + '_>> {
+        template.native_window = Some(raw_window_handle);
+        let configs = unsafe { self.find_configs(template)? };
+
+        #[cfg(x11_platform)]
+        {
+            use crate::platform::x11::X11GlConfigExt;
+
+            let visual_id = match raw_window_handle {
+                RawWindowHandle::Xlib(handle) => Some(handle.visual_id),
+                RawWindowHandle::Xcb(handle) => {
+                    handle.visual_id.map(|id| ffi::c_ulong::from(id.get()))
+                },
+                _ => None,
+            };
+
+            if let Some(visual_id) = visual_id.filter(|&id| id != 0) {
+                return Ok(Box::new(configs.filter(move |config| {
+                    config.x11_visual().is_some_and(|visual| visual.visual_id() == visual_id)
+                })));
+            }
+        }
+
+        Ok(configs)
+    }
+
+    /// The same as [`GlDisplay::find_configs`], but fails with
+    /// [`ErrorKind::NoMatchingConfig`] instead of returning no configs, so
+    /// the emptiness doesn't need to be special cased.
+    ///
+    /// The same error is returned by [`GlDisplay::find_configs`] itself on
+    /// GLX, WGL, and CGL, since the platform reports nothing matching as the
+    /// failure there.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::find_configs`].
+    ///
+    /// [`ErrorKind::NoMatchingConfig`]: crate::error::ErrorKind::NoMatchingConfig
+    pub unsafe fn find_configs_or_err(&self, template: ConfigTemplate) -> Result<Vec<Config>> {
+        let configs = unsafe { self.find_configs(template)? }.collect::<Vec<_>>();
+        if configs.is_empty() {
+            Err(crate::error::ErrorKind::NoMatchingConfig.into())
+        } else {
+            Ok(configs)
+        }
+    }
+
+    /// Find one config per each of the `templates`, such that all of them are
+    /// compatible, thus the surfaces created with them could be used with a
+    /// single context.
+    ///
+    /// The configs are compatible when they have the same color buffer type,
+    /// the same sizes of the color and ancillary buffers, and support a
+    /// common client Api. The configs are returned in the order of the
+    /// `templates`, the function fails with [`ErrorKind::NoMatchingConfig`]
+    /// when no compatible set exists.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::find_configs`].
+    ///
+    /// [`ErrorKind::NoMatchingConfig`]: crate::error::ErrorKind::NoMatchingConfig
+    pub unsafe fn find_compatible_configs(
+        &self,
+        templates: &[ConfigTemplate],
+    ) -> Result<Vec<Config>> {
+        let mut candidates = Vec::with_capacity(templates.len());
+        for template in templates {
+            candidates.push(unsafe { self.find_configs(template.clone())? }.collect::<Vec<_>>());
+        }
+
+        let (first, rest) = match candidates.split_first() {
+            Some(candidates) => candidates,
+            None => return Ok(Vec::new()),
+        };
+
+        let compatibility_key = |config: &Config| {
+            (
+                config.color_buffer_type(),
+                config.float_pixels(),
+                config.alpha_size(),
+                config.depth_size(),
+                config.stencil_size(),
+            )
+        };
+
+        'outer: for config in first {
+            let key = compatibility_key(config);
+            let mut api = config.api();
+            let mut configs = vec![config.clone()];
+            for candidates in rest {
+                let compatible = candidates.iter().find(|candidate| {
+                    compatibility_key(candidate) == key && candidate.api().intersects(api)
+                });
+                match compatible {
+                    Some(compatible) => {
+                        api &= compatible.api();
+                        configs.push(compatible.clone());
+                    },
+                    None => continue 'outer,
+                }
+            }
+
+            return Ok(configs);
+        }
+
+        let message = "no compatible set of configs matches the templates".to_owned();
+        Err(crate::error::Error::new(
+            None,
+            Some(message),
+            crate::error::ErrorKind::NoMatchingConfig,
+        ))
+    }
+
     /// Create a graphics platform display from the given raw display handle.
     ///
     /// The display mixing isn't supported, so if you created EGL display you
@@ -211,24 +606,35 @@ impl Display {
         match preference {
             #[cfg(egl_backend)]
             DisplayApiPreference::Egl => unsafe { Ok(Self::Egl(EglDisplay::new(display)?)) },
+            #[cfg(egl_backend)]
+            DisplayApiPreference::EglPlatform(platform) => unsafe {
+                Ok(Self::Egl(EglDisplay::with_platform(display, platform)?))
+            },
             #[cfg(glx_backend)]
             DisplayApiPreference::Glx(registrar) => unsafe {
                 Ok(Self::Glx(GlxDisplay::new(display, registrar)?))
             },
+            #[cfg(glx_backend)]
+            DisplayApiPreference::GlxWithOptions(registrar, options) => unsafe {
+                Ok(Self::Glx(GlxDisplay::with_options(display, registrar, options)?))
+            },
             #[cfg(all(egl_backend, glx_backend))]
             DisplayApiPreference::GlxThenEgl(registrar) => unsafe {
-                if let Ok(display) = GlxDisplay::new(display, registrar) {
-                    Ok(Self::Glx(display))
-                } else {
-                    Ok(Self::Egl(EglDisplay::new(display)?))
+                match GlxDisplay::new(display, registrar) {
+                    Ok(display) => Ok(Self::Glx(display)),
+                    Err(err) => Ok(Self::Egl(
+                        EglDisplay::new(display).map_err(|next| next.with_source(err))?,
+                    )),
                 }
             },
             #[cfg(all(egl_backend, glx_backend))]
             DisplayApiPreference::EglThenGlx(registrar) => unsafe {
-                if let Ok(display) = EglDisplay::new(display) {
-                    Ok(Self::Egl(display))
-                } else {
-                    Ok(Self::Glx(GlxDisplay::new(display, registrar)?))
+                match EglDisplay::new(display) {
+                    Ok(display) => Ok(Self::Egl(display)),
+                    Err(err) => Ok(Self::Glx(
+                        GlxDisplay::new(display, registrar)
+                            .map_err(|next| next.with_source(err))?,
+                    )),
                 }
             },
             #[cfg(wgl_backend)]
@@ -237,24 +643,47 @@ impl Display {
             },
             #[cfg(all(egl_backend, wgl_backend))]
             DisplayApiPreference::EglThenWgl(window_handle) => unsafe {
-                if let Ok(display) = EglDisplay::new(display) {
-                    Ok(Self::Egl(display))
-                } else {
-                    Ok(Self::Wgl(WglDisplay::new(display, window_handle)?))
+                match EglDisplay::new(display) {
+                    Ok(display) => Ok(Self::Egl(display)),
+                    Err(err) => Ok(Self::Wgl(
+                        WglDisplay::new(display, window_handle)
+                            .map_err(|next| next.with_source(err))?,
+                    )),
                 }
             },
             #[cfg(all(egl_backend, wgl_backend))]
             DisplayApiPreference::WglThenEgl(window_handle) => unsafe {
-                if let Ok(display) = WglDisplay::new(display, window_handle) {
-                    Ok(Self::Wgl(display))
-                } else {
-                    Ok(Self::Egl(EglDisplay::new(display)?))
+                match WglDisplay::new(display, window_handle) {
+                    Ok(display) => Ok(Self::Wgl(display)),
+                    Err(err) => Ok(Self::Egl(
+                        EglDisplay::new(display).map_err(|next| next.with_source(err))?,
+                    )),
                 }
             },
             #[cfg(cgl_backend)]
             DisplayApiPreference::Cgl => unsafe { Ok(Self::Cgl(CglDisplay::new(display)?)) },
         }
     }
+
+    /// The same as [`Display::new`], but the backend could be forced with the
+    /// `GLUTIN_BACKEND` environment variable set to `egl`, `glx`, or `wgl`,
+    /// which is useful to reproduce the driver issues without recompiling.
+    ///
+    /// When the variable is not set the `preference` is used as is. The
+    /// values which can't be used, like the unknown backends, the backends not
+    /// compiled in, or `glx` when the `preference` doesn't carry the
+    /// [`XlibErrorHookRegistrar`], are ignored with a warning logged when the
+    /// `log` feature is enabled.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn new_with_env_override(
+        display: RawDisplayHandle,
+        preference: DisplayApiPreference,
+    ) -> Result<Self> {
+        unsafe { Self::new(display, preference.with_env_override()) }
+    }
 }
 
 impl GlDisplay for Display {
@@ -413,6 +842,165 @@ impl AsRawDisplay for Display {
 
 impl Sealed for Display {}
 
+/// The EGL and GLX displays kept alive together for the same X11 display.
+///
+/// Unlike [`DisplayApiPreference::EglThenGlx`], which picks the backend once
+/// the display is created, the hybrid display retries the context and the
+/// window surface creation on GLX when it fails on EGL.
+///
+/// The objects created by different backends can't be mixed, thus the
+/// surfaces must be created with the display of the picked [`Config`], which
+/// could be obtained with [`GetGlDisplay::display`].
+#[cfg(all(egl_backend, glx_backend))]
+#[derive(Debug, Clone)]
+pub struct HybridDisplay {
+    displays: Vec<Display>,
+}
+
+#[cfg(all(egl_backend, glx_backend))]
+impl HybridDisplay {
+    /// Create both EGL and GLX displays for the given raw display handle.
+    ///
+    /// The creation fails only when neither of the displays could be
+    /// created.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn new(
+        display: RawDisplayHandle,
+        registrar: XlibErrorHookRegistrar,
+    ) -> Result<Self> {
+        let mut displays = Vec::with_capacity(2);
+
+        let egl_error = match unsafe { EglDisplay::new(display) } {
+            Ok(display) => {
+                displays.push(Display::Egl(display));
+                None
+            },
+            Err(err) => Some(err),
+        };
+
+        match unsafe { GlxDisplay::new(display, registrar) } {
+            Ok(display) => displays.push(Display::Glx(display)),
+            Err(err) if displays.is_empty() => {
+                return Err(match egl_error {
+                    Some(egl_error) => err.with_source(egl_error),
+                    None => err,
+                })
+            },
+            Err(_) => (),
+        }
+
+        Ok(Self { displays })
+    }
+
+    /// The displays in the order they are tried.
+    pub fn displays(&self) -> &[Display] {
+        &self.displays
+    }
+
+    /// Find the config with the `picker` and create the context with it,
+    /// moving to the next display when either of those fails.
+    ///
+    /// The returned error carries the errors from the previous displays as its
+    /// [`source`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    ///
+    /// [`source`]: std::error::Error::source
+    pub unsafe fn create_context<P>(
+        &self,
+        template: ConfigTemplate,
+        context_attributes: &ContextAttributes,
+        picker: P,
+    ) -> Result<(Config, NotCurrentContext)>
+    where
+        P: FnMut(Box<dyn Iterator<Item = Config> + '_>) -> Option<Config>,
+    {
+        self.with_first_display(template, picker, |display, config| unsafe {
+            display.create_context(config, context_attributes)
+        })
+    }
+
+    /// Find the config with the `picker` and create the window surface with
+    /// it, moving to the next display when either of those fails.
+    ///
+    /// The context used with the surface must be created with the display of
+    /// the returned config. The returned error carries the errors from the
+    /// previous displays as its [`source`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_window_surface`].
+    ///
+    /// [`source`]: std::error::Error::source
+    pub unsafe fn create_window_surface<P>(
+        &self,
+        template: ConfigTemplate,
+        surface_attributes: &SurfaceAttributes<WindowSurface>,
+        picker: P,
+    ) -> Result<(Config, Surface<WindowSurface>)>
+    where
+        P: FnMut(Box<dyn Iterator<Item = Config> + '_>) -> Option<Config>,
+    {
+        self.with_first_display(template, picker, |display, config| unsafe {
+            display.create_window_surface(config, surface_attributes)
+        })
+    }
+
+    /// Pick the config and `create` the object with it on the first display
+    /// where both of those succeed.
+    fn with_first_display<P, T>(
+        &self,
+        template: ConfigTemplate,
+        mut picker: P,
+        mut create: impl FnMut(&Display, &Config) -> Result<T>,
+    ) -> Result<(Config, T)>
+    where
+        P: FnMut(Box<dyn Iterator<Item = Config> + '_>) -> Option<Config>,
+    {
+        first_success(&self.displays, crate::error::ErrorKind::NotFound, |display| {
+            let configs = unsafe { display.find_configs(template.clone())? };
+            let config = picker(configs).ok_or(crate::error::ErrorKind::NoMatchingConfig)?;
+            let object = create(display, &config)?;
+            Ok((config, object))
+        })
+    }
+}
+
+/// Run the `attempt` for the `items` in order until it succeeds.
+///
+/// When all the attempts fail, the error of the last one is returned with the
+/// errors of the previous ones chained as its source, and the `empty` error is
+/// returned when there were no `items`.
+pub(crate) fn first_success<I: IntoIterator, T>(
+    items: I,
+    empty: crate::error::ErrorKind,
+    mut attempt: impl FnMut(I::Item) -> Result<T>,
+) -> Result<T> {
+    let mut error: Option<crate::error::Error> = None;
+    for item in items {
+        match attempt(item) {
+            Ok(result) => return Ok(result),
+            Err(err) => {
+                error = Some(match error.take() {
+                    Some(previous) => err.with_source(previous),
+                    None => err,
+                })
+            },
+        }
+    }
+
+    Err(error.unwrap_or_else(|| empty.into()))
+}
+
+/// The environment variable to force the backend with
+/// [`Display::new_with_env_override`].
+static GLUTIN_BACKEND_ENV: &str = "GLUTIN_BACKEND";
+
 /// Preference of the display that should be used.
 pub enum DisplayApiPreference {
     /// Use only EGL.
@@ -434,6 +1022,15 @@ pub enum DisplayApiPreference {
     #[cfg(egl_backend)]
     Egl,
 
+    /// Use only EGL with the given platform, instead of the one inferred
+    /// from the [`RawDisplayHandle`].
+    ///
+    /// See [`EglDisplay::with_platform`] for details.
+    ///
+    /// [`EglDisplay::with_platform`]: crate::api::egl::display::Display::with_platform
+    #[cfg(egl_backend)]
+    EglPlatform(EglPlatform),
+
     /// Use only GLX.
     ///
     /// The native GLX platform, it's not very optimal since it's usually tied
@@ -446,6 +1043,24 @@ pub enum DisplayApiPreference {
     #[cfg(glx_backend)]
     Glx(XlibErrorHookRegistrar),
 
+    /// Use only GLX with the given [`GlxOptions`].
+    ///
+    /// The same as [`Glx`], which uses the default options.
+    ///
+    /// ```no_run
+    /// # use glutin::api::glx::XlibErrorHookRegistrar;
+    /// use glutin::api::glx::display::GlxOptions;
+    /// use glutin::display::DisplayApiPreference;
+    ///
+    /// # let registrar: XlibErrorHookRegistrar = unimplemented!();
+    /// let options = GlxOptions::new().with_virtualbox_workaround(false);
+    /// let preference = DisplayApiPreference::GlxWithOptions(registrar, options);
+    /// ```
+    ///
+    /// [`Glx`]: Self::Glx
+    #[cfg(glx_backend)]
+    GlxWithOptions(XlibErrorHookRegistrar, GlxOptions),
+
     /// Use only WGL.
     ///
     /// The most spread platform on Windows and what should be used on it by
@@ -500,13 +1115,61 @@ pub enum DisplayApiPreference {
     WglThenEgl(Option<raw_window_handle::RawWindowHandle>),
 }
 
+impl DisplayApiPreference {
+    /// Override the preference with the backend from the `GLUTIN_BACKEND`
+    /// environment variable, keeping the backend options of the `self`.
+    fn with_env_override(self) -> Self {
+        let backend = match env::var(GLUTIN_BACKEND_ENV) {
+            Ok(backend) => backend,
+            Err(_) => return self,
+        };
+
+        match (backend.to_ascii_lowercase().as_str(), self) {
+            #[cfg(egl_backend)]
+            ("egl", preference @ Self::EglPlatform(_)) => preference,
+            #[cfg(egl_backend)]
+            ("egl", _) => Self::Egl,
+            #[cfg(glx_backend)]
+            ("glx", Self::Glx(registrar)) => Self::Glx(registrar),
+            #[cfg(glx_backend)]
+            ("glx", preference @ Self::GlxWithOptions(..)) => preference,
+            #[cfg(all(egl_backend, glx_backend))]
+            ("glx", Self::EglThenGlx(registrar) | Self::GlxThenEgl(registrar)) => {
+                Self::Glx(registrar)
+            },
+            #[cfg(wgl_backend)]
+            ("wgl", Self::Wgl(window_handle)) => Self::Wgl(window_handle),
+            #[cfg(all(egl_backend, wgl_backend))]
+            ("wgl", Self::EglThenWgl(window_handle) | Self::WglThenEgl(window_handle)) => {
+                Self::Wgl(window_handle)
+            },
+            #[cfg(wgl_backend)]
+            ("wgl", _) => Self::Wgl(None),
+            (_, preference) => {
+                #[cfg(feature = "log")]
+                log::warn!("ignoring {GLUTIN_BACKEND_ENV}={backend}, using {preference:?}");
+                preference
+            },
+        }
+    }
+}
+
 impl fmt::Debug for DisplayApiPreference {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let api = match self {
             #[cfg(egl_backend)]
             DisplayApiPreference::Egl => "Egl",
+            #[cfg(egl_backend)]
+            DisplayApiPreference::EglPlatform(platform) => {
+                return f.write_fmt(format_args!("DisplayApiPreference::EglPlatform({platform:?})"))
+            },
             #[cfg(glx_backend)]
             DisplayApiPreference::Glx(_) => "Glx",
+            #[cfg(glx_backend)]
+            DisplayApiPreference::GlxWithOptions(_, options) => {
+                return f
+                    .write_fmt(format_args!("DisplayApiPreference::GlxWithOptions({options:?})"))
+            },
             #[cfg(all(egl_backend, glx_backend))]
             DisplayApiPreference::GlxThenEgl(_) => "GlxThenEgl",
             #[cfg(all(egl_backend, glx_backend))]
@@ -568,9 +1231,92 @@ bitflags! {
         ///
         /// [`SRGB`]: crate::surface::SurfaceAttributesBuilder::with_srgb
         const SRGB_FRAMEBUFFERS           = 0b1000_0000;
+
+        /// The display supports making the context current with the distinct
+        /// draw and read surfaces via [`make_current_draw_read`].
+        ///
+        /// [`make_current_draw_read`]: crate::context::PossiblyCurrentGlContext::make_current_draw_read
+        const SEPARATE_READ_SURFACE       = 0b1_0000_0000;
     }
 }
 
+bitflags! {
+    /// The graphics platform interfaces glutin could use.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BackendFlags: u8 {
+        /// The EGL backend.
+        const EGL = 0b0001;
+
+        /// The GLX backend.
+        const GLX = 0b0010;
+
+        /// The WGL backend.
+        const WGL = 0b0100;
+
+        /// The CGL backend.
+        const CGL = 0b1000;
+    }
+}
+
+/// The backends glutin was built with, based on the target and the enabled
+/// features.
+pub const COMPILED_BACKENDS: BackendFlags = {
+    #[allow(unused_mut)]
+    let mut backends = BackendFlags::empty();
+    #[cfg(egl_backend)]
+    {
+        backends = backends.union(BackendFlags::EGL);
+    }
+    #[cfg(glx_backend)]
+    {
+        backends = backends.union(BackendFlags::GLX);
+    }
+    #[cfg(wgl_backend)]
+    {
+        backends = backends.union(BackendFlags::WGL);
+    }
+    #[cfg(cgl_backend)]
+    {
+        backends = backends.union(BackendFlags::CGL);
+    }
+    backends
+};
+
+/// The [`COMPILED_BACKENDS`] which libraries could be loaded on the system.
+///
+/// The backends being present here doesn't guarantee that the [`Display`]
+/// could be created with them, since it also depends on the native display.
+pub fn enabled_backends() -> BackendFlags {
+    #[allow(unused_mut)]
+    let mut backends = COMPILED_BACKENDS;
+
+    #[cfg(egl_backend)]
+    if crate::api::egl::EGL.is_none() {
+        backends.remove(BackendFlags::EGL);
+    }
+
+    #[cfg(glx_backend)]
+    if crate::api::glx::GLX.is_none() {
+        backends.remove(BackendFlags::GLX);
+    }
+
+    backends
+}
+
+/// The status of the GPU device backing the display, see
+/// [`Display::check_device_reset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceResetStatus {
+    /// The device wasn't reset.
+    NoReset,
+
+    /// The device was reset, thus the display must be recreated.
+    Reset,
+
+    /// The reset status can't be queried on the platform.
+    Unknown,
+}
+
 /// Raw GL platform display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RawDisplay {
@@ -1,5 +1,6 @@
 //! Glutin error handling.
 
+use std::cell::RefCell;
 use std::fmt;
 
 /// A specialized [`Result`] type for graphics operations.
@@ -16,6 +17,9 @@ pub struct Error {
 
     /// The simplified error kind to handle matching.
     kind: ErrorKind,
+
+    /// The error which caused this one, for example a failed earlier attempt.
+    source: Option<Box<Error>>,
 }
 
 impl Error {
@@ -25,7 +29,35 @@ impl Error {
         raw_os_message: Option<String>,
         kind: ErrorKind,
     ) -> Self {
-        Self { raw_code, raw_os_message, kind }
+        Self { raw_code, raw_os_message, kind, source: None }
+    }
+
+    /// Attach the error that preceded this one.
+    ///
+    /// The attached error is reported through
+    /// [`std::error::Error::source`], so when trying multiple
+    /// [`ContextAttributes`] in a row the reasons for every failed attempt
+    /// could be retained, not just the last one.
+    ///
+    /// ```no_run
+    /// # use glutin::error::{Error, ErrorKind};
+    /// # fn attempt(_: u32) -> Result<(), Error> { Err(ErrorKind::BadMatch.into()) }
+    /// let result = attempt(0).or_else(|first| attempt(1).map_err(|err| err.with_source(first)));
+    ///
+    /// if let Err(err) = result {
+    ///     let mut source = Some(&err as &dyn std::error::Error);
+    ///     while let Some(err) = source {
+    ///         eprintln!("{err}");
+    ///         source = err.source();
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`ContextAttributes`]: crate::context::ContextAttributes
+    #[inline]
+    pub fn with_source(mut self, source: Error) -> Self {
+        self.source = Some(Box::new(source));
+        self
     }
 
     /// Helper to check that error is [`ErrorKind::NotSupported`].
@@ -34,6 +66,20 @@ impl Error {
         matches!(&self.kind, ErrorKind::NotSupported(_))
     }
 
+    /// Helper to check that the surface is no longer valid.
+    ///
+    /// This happens when the native window got destroyed behind the back,
+    /// for example when the output it was presented on got disconnected, so
+    /// the surface should be recreated instead of treating it as a fatal error.
+    /// The raw code of the error is available with [`Self::raw_code`].
+    #[inline]
+    pub fn surface_lost(&self) -> bool {
+        matches!(
+            &self.kind,
+            ErrorKind::BadSurface | ErrorKind::BadCurrentSurface | ErrorKind::BadNativeWindow
+        )
+    }
+
     /// The underlying error kind.
     #[inline]
     pub fn error_kind(&self) -> ErrorKind {
@@ -45,6 +91,55 @@ impl Error {
     pub fn raw_code(&self) -> Option<i64> {
         self.raw_code
     }
+
+    /// Remember the error as the last [`NativeError`] of the `api` on the
+    /// current thread.
+    pub(crate) fn record_native(self, api: &'static str) -> Self {
+        if let Some(code) = self.raw_code {
+            let message = self.raw_os_message.clone();
+            LAST_NATIVE_ERROR
+                .with(|last| *last.borrow_mut() = Some(NativeError { api, code, message }));
+        }
+
+        self
+    }
+}
+
+thread_local! {
+    /// The last error reported by the graphics platform interface.
+    static LAST_NATIVE_ERROR: RefCell<Option<NativeError>> = const { RefCell::new(None) };
+}
+
+/// Get the last [`NativeError`] of the `api` observed on the current thread.
+pub(crate) fn last_native_error(api: &str) -> Option<NativeError> {
+    LAST_NATIVE_ERROR.with(|last| last.borrow().as_ref().filter(|error| error.api == api).cloned())
+}
+
+/// The raw error as reported by the graphics platform interface.
+///
+/// Unlike [`Error`] it keeps the name of the Api it came from, which is
+/// useful to report the issues against the drivers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeError {
+    /// The name of the Api reported the error, like `EGL`.
+    pub api: &'static str,
+
+    /// The raw code of the error, like the value of `eglGetError`.
+    pub code: i64,
+
+    /// The description of the error, when it was provided by the Api.
+    pub message: Option<String>,
+}
+
+impl fmt::Display for NativeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} error {:#x}", self.api, self.code)?;
+        if let Some(message) = self.message.as_ref() {
+            write!(f, ": {message}")?;
+        }
+
+        Ok(())
+    }
 }
 
 impl fmt::Display for Error {
@@ -63,12 +158,16 @@ impl fmt::Display for Error {
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
 
 /// Build an error with just a kind.
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Self {
-        Error { raw_code: None, raw_os_message: None, kind }
+        Error { raw_code: None, raw_os_message: None, kind, source: None }
     }
 }
 
@@ -139,6 +238,13 @@ pub enum ErrorKind {
     /// The operation is not supported by the platform.
     NotSupported(&'static str),
 
+    /// The requested combination of the attributes is invalid on any
+    /// platform.
+    BadApiUsage(&'static str),
+
+    /// No configs match the requested template.
+    NoMatchingConfig,
+
     /// The misc error that can't be classified occurred.
     Misc,
 }
@@ -166,6 +272,8 @@ impl ErrorKind {
             BadNativeWindow => "argument does not refer to a valid native window",
             ContextLost => "context loss",
             NotSupported(reason) => reason,
+            BadApiUsage(reason) => reason,
+            NoMatchingConfig => "no configs match the template",
             Misc => "misc platform error",
         }
     }
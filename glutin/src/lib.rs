@@ -13,6 +13,9 @@
 //! ## Environment variables
 //!
 //! `GLUTIN_WGL_OPENGL_DLL` - change the name of the OpenGL DLL to load.
+//!
+//! `GLUTIN_BACKEND` - force the `egl`, `glx`, or `wgl` backend when creating the
+//! display with [`display::Display::new_with_env_override`].
 
 #![deny(rust_2018_idioms)]
 #![deny(rustdoc::broken_intra_doc_links)]
@@ -24,7 +27,16 @@
 #![cfg_attr(clippy, deny(warnings))]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
-#[cfg(all(not(egl_backend), not(glx_backend), not(wgl_backend), not(cgl_backend)))]
+#[cfg(wasm_platform)]
+compile_error!("There's no WebGL backend, create the context from the canvas directly");
+
+#[cfg(all(
+    not(egl_backend),
+    not(glx_backend),
+    not(wgl_backend),
+    not(cgl_backend),
+    not(wasm_platform)
+))]
 compile_error!("Please select at least one api backend");
 
 pub mod api;
@@ -36,6 +48,8 @@ pub mod platform;
 pub mod prelude;
 pub mod surface;
 
+pub use display::{enabled_backends, BackendFlags, COMPILED_BACKENDS};
+
 #[cfg(any(egl_backend, glx_backend))]
 mod lib_loading;
 
@@ -3,7 +3,7 @@
 use std::mem;
 
 use once_cell::sync::Lazy;
-use x11_dl::xlib::{Display, XVisualInfo, Xlib};
+use x11_dl::xlib::{self, Display, XVisualInfo, Xlib};
 #[cfg(egl_backend)]
 use x11_dl::xlib::{VisualIDMask, XID};
 use x11_dl::xrender::Xrender;
@@ -18,6 +18,31 @@ static XRENDER: Lazy<Option<Xrender>> = Lazy::new(|| Xrender::open().ok());
 pub trait X11GlConfigExt {
     /// The `X11VisualInfo` that must be used to initialize the Xlib window.
     fn x11_visual(&self) -> Option<X11VisualInfo>;
+
+    /// The depth of the config's visual.
+    ///
+    /// The window must be created with the depth of `32` for the compositor
+    /// to blend it, thus a config with such visual should be picked when
+    /// transparency is desired.
+    fn visual_depth(&self) -> Option<u8> {
+        self.x11_visual().map(|visual| visual.depth())
+    }
+
+    /// The class of the config's visual.
+    fn x11_visual_type(&self) -> Option<X11VisualType> {
+        self.x11_visual().and_then(|visual| visual.visual_type())
+    }
+}
+
+/// The class of the RGB X11 visual.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum X11VisualType {
+    /// The colors are passed to the display as is.
+    #[default]
+    TrueColor,
+
+    /// The colors are mapped through the modifiable colormap.
+    DirectColor,
 }
 
 /// The X11 visual info.
@@ -67,6 +92,20 @@ impl X11VisualInfo {
         self.transparency
     }
 
+    /// The depth of the visual in bits.
+    pub fn depth(&self) -> u8 {
+        unsafe { (*self.raw).depth as u8 }
+    }
+
+    /// The class of the visual, `None` when it's not the RGB one.
+    pub fn visual_type(&self) -> Option<X11VisualType> {
+        match unsafe { (*self.raw).class } {
+            xlib::TrueColor => Some(X11VisualType::TrueColor),
+            xlib::DirectColor => Some(X11VisualType::DirectColor),
+            _ => None,
+        }
+    }
+
     /// Get XID of for this visual.
     pub fn visual_id(&self) -> std::ffi::c_ulong {
         unsafe { (*self.raw).visualid }
@@ -86,9 +125,7 @@ impl X11VisualInfo {
         unsafe {
             let visual_format = (xrender.XRenderFindVisualFormat)(display, (*raw).visual);
 
-            (!visual_format.is_null())
-                .then(|| (*visual_format).direct.alphaMask != 0)
-                .unwrap_or(false)
+            !visual_format.is_null() && (*visual_format).direct.alphaMask != 0
         }
     }
 }
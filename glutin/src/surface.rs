@@ -3,12 +3,17 @@
 
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use raw_window_handle::RawWindowHandle;
 
-use crate::context::{PossiblyCurrentContext, PossiblyCurrentGlContext};
-use crate::display::{Display, GetGlDisplay};
-use crate::error::Result;
+use crate::config::{Config, GetGlConfig};
+use crate::context::{
+    ContextAttributes, NotCurrentContext, PossiblyCurrentContext, PossiblyCurrentGlContext,
+};
+use crate::display::{Display, GetGlDisplay, GlDisplay};
+use crate::error::{Error, ErrorKind, Result};
 use crate::private::{gl_api_dispatch, Sealed};
 
 #[cfg(cgl_backend)]
@@ -57,6 +62,11 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
 
     /// Swaps the underlying back buffers when the surface is not single
     /// buffered.
+    ///
+    /// When the surface got lost, which could be checked with
+    /// [`Error::surface_lost`], it should be recreated.
+    ///
+    /// [`Error::surface_lost`]: crate::error::Error::surface_lost
     fn swap_buffers(&self, context: &Self::Context) -> Result<()>;
 
     /// Check whether the surface is current on to the current thread.
@@ -72,6 +82,10 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
 
     /// Set swap interval for the surface.
     ///
+    /// The `context` must be current with the surface, otherwise the interval
+    /// may not apply to it. To not track that during the setup use
+    /// [`Surface::configure_swap_interval`].
+    ///
     /// See [`crate::surface::SwapInterval`] for details.
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()>;
 
@@ -102,6 +116,10 @@ pub trait SurfaceTypeTrait: Sealed {
 pub trait ResizeableSurface: Sealed {}
 
 /// Trait for accessing the raw GL surface.
+///
+/// This could be used to interop with the code operating on the underlying
+/// platform surfaces directly, like capture layers or external presentation
+/// libraries.
 pub trait AsRawSurface {
     /// Get the raw handle to the surface.
     fn raw_surface(&self) -> RawSurface;
@@ -130,6 +148,23 @@ impl<T: SurfaceTypeTrait + Default> SurfaceAttributesBuilder<T> {
         self.attributes.srgb = srgb;
         self
     }
+
+    /// Request the protected surface, which contents can't be accessed by
+    /// the unprotected contexts, e.g. for the DRM protected video playback.
+    ///
+    /// The surface creation fails when the protected content isn't
+    /// supported.
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Api-specific.
+    ///
+    /// - **EGL:** requires `EGL_EXT_protected_content`.
+    /// - **GLX/WGL/CGL:** not supported.
+    pub fn with_protected(mut self, protected: bool) -> Self {
+        self.attributes.protected = protected;
+        self
+    }
 }
 
 impl SurfaceAttributesBuilder<WindowSurface> {
@@ -164,6 +199,27 @@ impl SurfaceAttributesBuilder<WindowSurface> {
 
 impl SurfaceAttributesBuilder<PbufferSurface> {
     /// Request the largest pbuffer.
+    ///
+    /// When the requested size can't be allocated, the largest available
+    /// pbuffer is created instead of failing, preserving the aspect ratio.
+    /// The allocated size is reported by [`Surface::size`].
+    ///
+    /// ```no_run
+    /// # use std::num::NonZeroU32;
+    /// # use glutin::prelude::*;
+    /// use glutin::surface::{PbufferSurface, SurfaceAttributesBuilder};
+    /// # let display: glutin::display::Display = unimplemented!();
+    /// # let config: glutin::config::Config = unimplemented!();
+    ///
+    /// let size = NonZeroU32::new(1 << 20).unwrap();
+    /// let attrs = SurfaceAttributesBuilder::<PbufferSurface>::new()
+    ///     .with_largest_pbuffer(true)
+    ///     .build(size, size);
+    /// let surface = unsafe { display.create_pbuffer_surface(&config, &attrs).unwrap() };
+    /// let (width, height) = surface.size().unwrap();
+    /// assert!(width > 0 && width < size.get());
+    /// assert!(height > 0 && height < size.get());
+    /// ```
     pub fn with_largest_pbuffer(mut self, largest_pbuffer: bool) -> Self {
         self.attributes.largest_pbuffer = largest_pbuffer;
         self
@@ -204,11 +260,74 @@ pub struct SurfaceAttributes<T: SurfaceTypeTrait> {
     pub(crate) width: Option<NonZeroU32>,
     pub(crate) height: Option<NonZeroU32>,
     pub(crate) largest_pbuffer: bool,
+    pub(crate) protected: bool,
     pub(crate) raw_window_handle: Option<RawWindowHandle>,
     pub(crate) native_pixmap: Option<NativePixmap>,
     _ty: PhantomData<T>,
 }
 
+impl<T: SurfaceTypeTrait> SurfaceAttributes<T> {
+    /// Fail when the protected surface is requested on the platform without
+    /// the support for it.
+    #[cfg(any(glx_backend, wgl_backend, cgl_backend))]
+    pub(crate) fn check_protected_unsupported(&self) -> Result<()> {
+        if self.protected {
+            Err(crate::error::ErrorKind::NotSupported("protected content is not supported").into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl SurfaceAttributes<PbufferSurface> {
+    /// Check the requested size against the `max_pbuffer_size` of the config.
+    ///
+    /// The check is skipped when the largest pbuffer is requested, since
+    /// the size gets clamped to the supported one in this case.
+    #[cfg(any(egl_backend, glx_backend, wgl_backend))]
+    pub(crate) fn check_max_pbuffer_size(
+        &self,
+        max_pbuffer_size: Option<(u32, u32, u32)>,
+    ) -> Result<()> {
+        let (max_width, max_height, max_pixels) = match max_pbuffer_size {
+            Some(max_pbuffer_size) if !self.largest_pbuffer => max_pbuffer_size,
+            _ => return Ok(()),
+        };
+
+        let width = self.width.unwrap().get();
+        let height = self.height.unwrap().get();
+        if width > max_width
+            || height > max_height
+            || width as u64 * height as u64 > max_pixels as u64
+        {
+            let message = format!(
+                "requested pbuffer size {width}x{height} exceeds the config limit of \
+                 {max_width}x{max_height} with at most {max_pixels} pixels"
+            );
+            let kind = crate::error::ErrorKind::BadAttribute;
+            return Err(crate::error::Error::new(None, Some(message), kind));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the multisampled `config` could back the pbuffer, since
+    /// some drivers support multisampling only for the window surfaces.
+    #[cfg(any(egl_backend, glx_backend, wgl_backend))]
+    pub(crate) fn check_pbuffer_samples(config: &impl crate::config::GlConfig) -> Result<()> {
+        use crate::config::ConfigSurfaceTypes;
+
+        let samples = config.num_samples();
+        if samples > 0 && !config.config_surface_types().contains(ConfigSurfaceTypes::PBUFFER) {
+            let message = format!("the config with {samples} samples doesn't support pbuffers");
+            let kind = crate::error::ErrorKind::BadConfig;
+            return Err(crate::error::Error::new(None, Some(message), kind));
+        }
+
+        Ok(())
+    }
+}
+
 /// Marker that used to type-gate methods for window.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WindowSurface;
@@ -336,7 +455,7 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()> {
-        match (self, context) {
+        let result = match (self, context) {
             #[cfg(egl_backend)]
             (Self::Egl(surface), PossiblyCurrentContext::Egl(context)) => {
                 surface.set_swap_interval(context, interval)
@@ -354,7 +473,14 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
                 surface.set_swap_interval(context, interval)
             },
             _ => unreachable!(),
+        };
+
+        // Don't override the explicitly set interval with the context default.
+        if result.is_ok() {
+            self.swap_interval_set().store(true, Ordering::Relaxed);
         }
+
+        result
     }
 
     fn is_current(&self, context: &Self::Context) -> bool {
@@ -449,6 +575,292 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 }
 
+impl<T: SurfaceTypeTrait> Surface<T> {
+    /// Whether the surface is still usable, e.g. after the display
+    /// disconnect or when its native window could have been destroyed.
+    ///
+    /// The check is a lightweight query, so it could be done before
+    /// rendering every frame.
+    ///
+    /// # Api-specific
+    ///
+    /// - **CGL:** always `true`.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(surface) => surface.is_valid(),
+            #[cfg(glx_backend)]
+            Self::Glx(surface) => surface.is_valid(),
+            #[cfg(wgl_backend)]
+            Self::Wgl(surface) => surface.is_valid(),
+            _ => true,
+        }
+    }
+
+    /// The same as [`GlSurface::swap_buffers`], but returns how long the
+    /// native swap call blocked, which includes the wait for the vertical
+    /// blank when the vsync is enabled.
+    ///
+    /// Only the platform call is measured, so this could be used for the
+    /// frame time breakdown.
+    pub fn swap_buffers_timed(&self, context: &PossiblyCurrentContext) -> Result<Duration> {
+        let start = Instant::now();
+        self.swap_buffers(context)?;
+        Ok(start.elapsed())
+    }
+
+    /// The size of the surface in pixels, which is the allocated one for the
+    /// pbuffers created with
+    /// [`SurfaceAttributesBuilder::<PbufferSurface>::with_largest_pbuffer`].
+    ///
+    /// This is the [`GlSurface::width`] and [`GlSurface::height`] queried
+    /// together.
+    pub fn size(&self) -> Option<(u32, u32)> {
+        Some((self.width()?, self.height()?))
+    }
+
+    /// Apply the [`ContextAttributesBuilder::with_default_swap_interval`] of
+    /// the `context` to the window surface, unless some interval was already
+    /// set for it.
+    ///
+    /// [`ContextAttributesBuilder::with_default_swap_interval`]: crate::context::ContextAttributesBuilder::with_default_swap_interval
+    pub(crate) fn apply_default_swap_interval(
+        &self,
+        context: &PossiblyCurrentContext,
+    ) -> Result<()> {
+        if !matches!(T::surface_type(), SurfaceType::Window) {
+            return Ok(());
+        }
+
+        let interval = match context.context_attributes().default_swap_interval {
+            Some(interval) => interval,
+            None => return Ok(()),
+        };
+
+        if self.swap_interval_set().load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        // The interval is only a hint, so the failure is ignored unless in the strict
+        // mode.
+        match self.set_swap_interval(context, interval) {
+            Err(err) if context.context_attributes().strict => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn swap_interval_set(&self) -> &AtomicBool {
+        gl_api_dispatch!(self; Self(surface) => &surface.swap_interval_set)
+    }
+
+    /// The origin of the surface's coordinate system.
+    ///
+    /// This could be used to know whether the content read back from the
+    /// surface should be flipped.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** determined with `EGL_ANGLE_surface_orientation`, otherwise
+    ///   [`SurfaceOrigin::BottomLeft`].
+    /// - **GLX/WGL/CGL:** always [`SurfaceOrigin::BottomLeft`].
+    pub fn origin(&self) -> SurfaceOrigin {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(surface) => surface.origin(),
+            _ => SurfaceOrigin::BottomLeft,
+        }
+    }
+
+    /// Create the context with the same config the surface was created
+    /// with, so the context could always be made current against it.
+    ///
+    /// This is a shorthand for [`GlDisplay::create_context`] with the
+    /// surface's [`GetGlConfig::config`]. Contexts created from a different,
+    /// yet compatible, config could still be made current with the surface.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    pub unsafe fn create_compatible_context(
+        &self,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext> {
+        unsafe { self.display().create_context(&self.config(), context_attributes) }
+    }
+
+    /// Set the swap interval, making the `context` current with the surface
+    /// when it's not already.
+    ///
+    /// This is meant for the setup, when the context could be not current
+    /// yet. When the `context` wasn't current at all, it's made not current
+    /// again afterwards, otherwise it stays current with this surface, since
+    /// the surfaces it was current with before are not known.
+    pub fn configure_swap_interval(
+        &self,
+        context: &PossiblyCurrentContext,
+        interval: SwapInterval,
+    ) -> Result<()> {
+        if self.is_current_draw(context) {
+            return self.set_swap_interval(context, interval);
+        }
+
+        let was_current = context.is_current();
+        context.make_current(self)?;
+        let result = self.set_swap_interval(context, interval);
+        if !was_current {
+            context.make_not_current_in_place()?;
+        }
+
+        result
+    }
+
+    /// Swap the buffers like [`GlSurface::swap_buffers`] and report whether
+    /// the frame was actually presented.
+    ///
+    /// When the swap fails because the surface was lost, as reported by
+    /// [`Error::surface_lost`], the frame is reported as
+    /// [`SwapOutcome::Skipped`] carrying the error instead, so the render loop
+    /// could log it and recreate the surface. When the backend can't tell
+    /// what happened with the frame, [`SwapOutcome::Presented`] is returned.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL/GLX/WGL/CGL:** the swap provides no presentation feedback, thus
+    ///   the successful swap always reports [`SwapOutcome::Presented`].
+    pub fn swap_buffers_checked(&self, context: &PossiblyCurrentContext) -> Result<SwapOutcome> {
+        match self.swap_buffers(context) {
+            Ok(()) => Ok(SwapOutcome::Presented),
+            Err(err) if err.surface_lost() => Ok(SwapOutcome::Skipped(err)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Surface<WindowSurface> {
+    /// The refresh rate of the output the surface is currently presented on
+    /// in mHz.
+    ///
+    /// When `None` is returned, the refresh rate of the monitor the window is
+    /// on should be obtained from the windowing library instead.
+    ///
+    /// # Api-specific
+    ///
+    /// - **GLX:** requires `GLX_OML_sync_control`.
+    /// - **EGL/WGL/CGL:** the outputs are not tracked, thus `None` is always
+    ///   returned.
+    pub fn current_refresh_rate_millihz(&self) -> Option<u32> {
+        match self {
+            #[cfg(glx_backend)]
+            Self::Glx(surface) => surface.refresh_rate_millihz(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Replace the surface with the new one created for the native window
+    /// from the `surface_attributes`, using the same display and config.
+    ///
+    /// This is meant for the platforms where the native window could be
+    /// recreated during the application lifetime, like Android on resume.
+    /// The new surface gets the size passed in the `surface_attributes`, so
+    /// there's no need to [`resize`] it afterwards.
+    ///
+    /// The old surface is destroyed only once the new one was created, thus
+    /// on error `self` is left intact. The contexts current with the old
+    /// surface must be made current with the new one again.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_window_surface`].
+    ///
+    /// [`resize`]: GlSurface::resize
+    pub unsafe fn recreate(
+        &mut self,
+        surface_attributes: &SurfaceAttributes<WindowSurface>,
+    ) -> Result<()> {
+        let surface =
+            unsafe { self.display().create_window_surface(&self.config(), surface_attributes)? };
+        *self = surface;
+        Ok(())
+    }
+
+    /// Whether [`GlSurface::resize`] must be called for the surface to follow
+    /// the size of its window.
+    ///
+    /// When `false` the size is picked up from the window automatically and
+    /// resizing the surface is a no-op.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** `true` only on Wayland.
+    /// - **GLX/WGL:** always `false`.
+    /// - **CGL:** always `true`.
+    pub fn needs_resize(&self) -> bool {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(surface) => surface.needs_resize(),
+            #[cfg(glx_backend)]
+            Self::Glx(_) => false,
+            #[cfg(wgl_backend)]
+            Self::Wgl(_) => false,
+            #[cfg(cgl_backend)]
+            Self::Cgl(_) => true,
+        }
+    }
+
+    /// Make the surface a member of the swap `group`, where the buffer swaps
+    /// of all the members happen at the same time. Passing `0` removes the
+    /// surface from its current group.
+    ///
+    /// # Api-specific
+    ///
+    /// - **GLX:** requires `GLX_NV_swap_group`.
+    /// - **WGL:** requires `WGL_NV_swap_group`.
+    /// - **EGL/CGL:** not supported.
+    pub fn join_swap_group(&self, group: u32) -> Result<()> {
+        match self {
+            #[cfg(glx_backend)]
+            Self::Glx(surface) => surface.join_swap_group(group),
+            #[cfg(wgl_backend)]
+            Self::Wgl(surface) => surface.join_swap_group(group),
+            _ => {
+                let _ = group;
+                Err(ErrorKind::NotSupported("swap groups are not supported").into())
+            },
+        }
+    }
+
+    /// Bind the swap `group` to the swap `barrier`, synchronizing the buffer
+    /// swaps across the groups bound to the same barrier. Passing `0` as the
+    /// `barrier` unbinds the group.
+    ///
+    /// # Api-specific
+    ///
+    /// - **GLX:** requires `GLX_NV_swap_group`.
+    /// - **WGL:** requires `WGL_NV_swap_group`.
+    /// - **EGL/CGL:** not supported.
+    pub fn bind_swap_barrier(&self, group: u32, barrier: u32) -> Result<()> {
+        match self {
+            #[cfg(glx_backend)]
+            Self::Glx(surface) => surface.bind_swap_barrier(group, barrier),
+            #[cfg(wgl_backend)]
+            Self::Wgl(surface) => surface.bind_swap_barrier(group, barrier),
+            _ => {
+                let _ = (group, barrier);
+                Err(ErrorKind::NotSupported("swap groups are not supported").into())
+            },
+        }
+    }
+}
+
+impl<T: SurfaceTypeTrait> GetGlConfig for Surface<T> {
+    type Target = Config;
+
+    fn config(&self) -> Self::Target {
+        gl_api_dispatch!(self; Self(surface) => surface.config(); as Config)
+    }
+}
+
 impl<T: SurfaceTypeTrait> GetGlDisplay for Surface<T> {
     type Target = Display;
 
@@ -495,6 +907,42 @@ pub enum SwapInterval {
     Wait(NonZeroU32),
 }
 
+/// The origin of the surface's coordinate system.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SurfaceOrigin {
+    /// The origin is in the bottom left corner, which is the default for
+    /// OpenGL.
+    #[default]
+    BottomLeft,
+
+    /// The origin is in the top left corner, thus the content is y-flipped.
+    TopLeft,
+}
+
+/// The outcome of the [`Surface::swap_buffers_checked`].
+#[derive(Debug, Clone)]
+pub enum SwapOutcome {
+    /// The frame was handed to the presentation engine.
+    Presented,
+
+    /// The frame was dropped without being presented, because the surface or
+    /// its native window is no longer valid.
+    ///
+    /// The error of the swap is kept, so its [`Error::raw_code`] could be
+    /// logged.
+    Skipped(Error),
+}
+
+/// The framebuffer object name to bind with `glBindFramebuffer` to render
+/// into the current surface again after the offscreen passes.
+///
+/// All the supported Apis (EGL, GLX, WGL, and CGL) present the surfaces
+/// through the default framebuffer provided by the window system, which is
+/// always named `0`. Only the platforms rendering into the application
+/// created framebuffer object, like iOS with `EAGLContext`, could differ, and
+/// those aren't supported.
+pub const DEFAULT_FRAMEBUFFER_OBJECT: u32 = 0;
+
 /// A platform native pixmap.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NativePixmap {
@@ -520,6 +968,8 @@ pub enum RawSurface {
     Glx(u64),
 
     /// Either a `HWND` or `HPBUFFEREXT` depending on [`SurfaceType`].
+    ///
+    /// The `HDC` of the surface could be obtained with WGL's `Surface::hdc`.
     #[cfg(wgl_backend)]
     Wgl(*const std::ffi::c_void),
 
@@ -530,7 +980,10 @@ pub enum RawSurface {
 
 /// The rect that is being used in various surface operations.
 ///
-/// The origin is in the bottom left of the surface.
+/// The origin is in the bottom left of the surface, like in the GL, so the
+/// rect could be passed to e.g. `glScissor` as is. Use [`Rect::to_top_left`]
+/// and [`Rect::from_top_left`] to convert from and to the windowing system
+/// coordinates.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Rect {
@@ -549,4 +1002,30 @@ impl Rect {
     pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
         Self { x, y, width, height }
     }
+
+    /// Create the rect from the one with the origin in the top left of the
+    /// surface, like used by the windowing systems.
+    ///
+    /// ```
+    /// use glutin::surface::Rect;
+    ///
+    /// let rect = Rect::from_top_left(0, 0, 10, 20, 100);
+    /// assert_eq!(rect, Rect::new(0, 80, 10, 20));
+    /// ```
+    pub fn from_top_left(x: i32, y: i32, width: i32, height: i32, surface_height: u32) -> Self {
+        Self::new(x, y, width, height).to_top_left(surface_height)
+    }
+
+    /// Convert the rect to the one with the origin in the top left of the
+    /// surface with the given height.
+    ///
+    /// ```
+    /// use glutin::surface::Rect;
+    ///
+    /// let rect = Rect::new(0, 80, 10, 20).to_top_left(100);
+    /// assert_eq!(rect, Rect::new(0, 0, 10, 20));
+    /// ```
+    pub fn to_top_left(self, surface_height: u32) -> Self {
+        Self { y: surface_height as i32 - self.y - self.height, ..self }
+    }
 }
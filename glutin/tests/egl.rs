@@ -0,0 +1,163 @@
+//! Tests running against the EGL device display, thus not requiring any
+//! window system.
+//!
+//! They require the driver exposing `EGL_EXT_device_enumeration`, so they're
+//! ignored by default. Run them with `cargo test -- --ignored`.
+
+#![cfg(egl_backend)]
+
+use std::ffi::{c_void, CStr, CString};
+use std::mem;
+use std::num::NonZeroU32;
+
+use glutin::api::egl::config::Config;
+use glutin::api::egl::context::PossiblyCurrentContext;
+use glutin::api::egl::device::Device;
+use glutin::api::egl::display::Display;
+use glutin::config::{Api, ConfigSurfaceTypes, ConfigTemplateBuilder};
+use glutin::context::{ContextApi, ContextAttributes, ContextAttributesBuilder, GlProfile};
+use glutin::prelude::*;
+use glutin::surface::{PbufferSurface, SurfaceAttributesBuilder};
+
+const VERSION: u32 = 0x1f02;
+const CONTEXT_PROFILE_MASK: u32 = 0x9126;
+const CONTEXT_COMPATIBILITY_PROFILE_BIT: i32 = 0x2;
+
+type GetString = unsafe extern "system" fn(u32) -> *const u8;
+type GetIntegerv = unsafe extern "system" fn(u32, *mut i32);
+
+fn display() -> Display {
+    let devices = Device::query_devices().expect("failed to query devices").collect::<Vec<_>>();
+    let device = devices.first().expect("no available devices");
+    unsafe { Display::with_device(device, None) }.expect("failed to create display")
+}
+
+fn config(display: &Display, api: Api) -> Config {
+    let template = ConfigTemplateBuilder::new().with_api(api).build();
+    unsafe { display.find_configs(template) }
+        .expect("failed to find configs")
+        .next()
+        .expect("no config supporting the api")
+}
+
+fn make_current(
+    display: &Display,
+    config: &Config,
+    attributes: &ContextAttributes,
+) -> PossiblyCurrentContext {
+    unsafe { display.create_context(config, attributes) }
+        .expect("failed to create context")
+        .make_current_surfaceless()
+        .expect("failed to make context current")
+}
+
+fn get_proc_address(display: &Display, name: &str) -> *const c_void {
+    let name = CString::new(name).unwrap();
+    display.get_proc_address(&name)
+}
+
+fn get_string(display: &Display, name: u32) -> String {
+    let get_string = get_proc_address(display, "glGetString");
+    assert!(!get_string.is_null());
+
+    unsafe {
+        let get_string: GetString = mem::transmute(get_string);
+        let string = get_string(name);
+        assert!(!string.is_null());
+        CStr::from_ptr(string.cast()).to_string_lossy().into_owned()
+    }
+}
+
+fn get_integer(display: &Display, name: u32) -> i32 {
+    let get_integerv = get_proc_address(display, "glGetIntegerv");
+    assert!(!get_integerv.is_null());
+
+    let mut value = 0;
+    unsafe {
+        let get_integerv: GetIntegerv = mem::transmute(get_integerv);
+        get_integerv(name, &mut value);
+    }
+    value
+}
+
+#[test]
+#[ignore = "requires the EGL device with the desktop OpenGL support"]
+fn compatibility_profile_exposes_fixed_function() {
+    let display = display();
+    let config = config(&display, Api::OPENGL);
+    let attributes = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::OpenGl(None))
+        .with_profile(GlProfile::Compatibility)
+        .with_strict(true)
+        .build(None);
+    let _context = make_current(&display, &config, &attributes);
+
+    let profile = get_integer(&display, CONTEXT_PROFILE_MASK);
+    assert_ne!(profile & CONTEXT_COMPATIBILITY_PROFILE_BIT, 0);
+    assert!(!get_proc_address(&display, "glBegin").is_null());
+}
+
+#[test]
+#[ignore = "requires the EGL device with both the OpenGL and OpenGL ES support"]
+fn switch_between_opengl_and_gles_contexts() {
+    let display = display();
+    let config = config(&display, Api::OPENGL | Api::GLES2);
+    let gl_attributes =
+        ContextAttributesBuilder::new().with_context_api(ContextApi::OpenGl(None)).build(None);
+    let gles_attributes =
+        ContextAttributesBuilder::new().with_context_api(ContextApi::Gles(None)).build(None);
+
+    let gl_context = make_current(&display, &config, &gl_attributes);
+    let gles_context = make_current(&display, &config, &gles_attributes);
+
+    for _ in 0..2 {
+        gl_context.make_current_surfaceless().expect("failed to make OpenGL context current");
+        assert!(!get_string(&display, VERSION).starts_with("OpenGL ES"));
+
+        gles_context.make_current_surfaceless().expect("failed to make OpenGL ES context current");
+        assert!(get_string(&display, VERSION).starts_with("OpenGL ES"));
+    }
+}
+
+#[test]
+#[ignore = "requires the EGL device with the debug context support"]
+fn debug_context_reports_debug_flag() {
+    let display = glutin::display::Display::Egl(display());
+    let template = ConfigTemplateBuilder::new().build();
+    let config = unsafe { display.find_configs(template) }
+        .expect("failed to find configs")
+        .next()
+        .expect("no available configs");
+
+    // The strict mode fails the creation when the debug context can't be requested.
+    let attributes = ContextAttributesBuilder::new().with_debug(true).with_strict(true).build(None);
+    let context = unsafe { display.create_context(&config, &attributes) }
+        .expect("failed to create debug context")
+        .make_current_surfaceless()
+        .expect("failed to make context current");
+
+    assert!(context.is_debug());
+}
+
+#[test]
+#[ignore = "requires the EGL device with the pbuffer support"]
+fn largest_pbuffer_is_smaller_than_requested() {
+    let display = glutin::display::Display::Egl(display());
+    let template =
+        ConfigTemplateBuilder::new().with_surface_type(ConfigSurfaceTypes::PBUFFER).build();
+    let config = unsafe { display.find_configs(template) }
+        .expect("failed to find configs")
+        .next()
+        .expect("no config supporting pbuffers");
+
+    let size = NonZeroU32::new(1 << 20).unwrap();
+    let attributes = SurfaceAttributesBuilder::<PbufferSurface>::new()
+        .with_largest_pbuffer(true)
+        .build(size, size);
+    let surface = unsafe { display.create_pbuffer_surface(&config, &attributes) }
+        .expect("failed to create the largest pbuffer");
+
+    let (width, height) = surface.size().expect("failed to query pbuffer size");
+    assert!(width > 0 && width < size.get());
+    assert!(height > 0 && height < size.get());
+}
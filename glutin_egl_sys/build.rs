@@ -21,6 +21,7 @@ fn main() {
         let mut file = File::create(dest.join("egl_bindings.rs")).unwrap();
         let reg = Registry::new(Api::Egl, (1, 5), Profile::Core, Fallbacks::All, [
             "EGL_ANDROID_native_fence_sync",
+            "EGL_ANGLE_device_d3d",
             "EGL_EXT_buffer_age",
             "EGL_EXT_create_context_robustness",
             "EGL_EXT_device_base",
@@ -29,11 +30,16 @@ fn main() {
             "EGL_EXT_device_enumeration",
             "EGL_EXT_device_query",
             "EGL_EXT_device_query_name",
+            "EGL_EXT_image_dma_buf_import",
+            "EGL_EXT_image_dma_buf_import_modifiers",
             "EGL_EXT_pixel_format_float",
             "EGL_EXT_platform_base",
             "EGL_EXT_platform_device",
             "EGL_EXT_platform_wayland",
             "EGL_EXT_platform_x11",
+            "EGL_EXT_protected_content",
+            "EGL_EXT_surface_CTA861_3_metadata",
+            "EGL_EXT_surface_SMPTE2086_metadata",
             "EGL_EXT_swap_buffers_with_damage",
             "EGL_IMG_context_priority",
             "EGL_KHR_create_context",
@@ -41,13 +47,19 @@ fn main() {
             "EGL_KHR_display_reference",
             "EGL_KHR_fence_sync",
             "EGL_KHR_image_base",
+            "EGL_KHR_no_config_context",
+            "EGL_KHR_partial_update",
             "EGL_KHR_platform_android",
             "EGL_KHR_platform_gbm",
             "EGL_KHR_platform_wayland",
             "EGL_KHR_platform_x11",
+            "EGL_KHR_stream",
+            "EGL_KHR_stream_consumer_gltexture",
+            "EGL_KHR_stream_producer_eglsurface",
             "EGL_KHR_swap_buffers_with_damage",
             "EGL_KHR_wait_sync",
             "EGL_MESA_platform_gbm",
+            "EGL_MESA_platform_surfaceless",
             "EGL_NV_context_priority_realtime",
         ]);
 
@@ -34,6 +34,11 @@ pub const PLATFORM_ANGLE_NATIVE_PLATFORM_TYPE_ANGLE: super::EGLenum = 0x348F;
 pub const PLATFORM_ANGLE_TYPE_DEFAULT_ANGLE: super::EGLenum = 0x3206;
 pub const PLATFORM_ANGLE_DEVICE_TYPE_HARDWARE_ANGLE: super::EGLenum = 0x320A;
 pub const PLATFORM_ANGLE_DEVICE_TYPE_NULL_ANGLE: super::EGLenum = 0x345E;
+// EGL_ANGLE_surface_orientation - https://chromium.googlesource.com/angle/angle/+/HEAD/extensions/EGL_ANGLE_surface_orientation.txt
+pub const OPTIMAL_SURFACE_ORIENTATION_ANGLE: super::EGLenum = 0x33A7;
+pub const SURFACE_ORIENTATION_ANGLE: super::EGLenum = 0x33A8;
+pub const SURFACE_ORIENTATION_INVERT_X_ANGLE: super::EGLenum = 0x0001;
+pub const SURFACE_ORIENTATION_INVERT_Y_ANGLE: super::EGLenum = 0x0002;
 
 mod wayland_storage {
     use super::FnPtr;
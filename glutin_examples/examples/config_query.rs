@@ -0,0 +1,72 @@
+fn main() {
+    #[cfg(egl_backend)]
+    example::run();
+}
+
+/// Compare reading the config attributes from the driver with reading them
+/// from the per config cache, for the single attribute picker and the picker
+/// inspecting all of them.
+#[cfg(egl_backend)]
+mod example {
+    use std::time::{Duration, Instant};
+
+    use glutin::api::egl::config::Config;
+    use glutin::api::egl::device::Device;
+    use glutin::api::egl::display::Display;
+    use glutin::config::ConfigTemplate;
+    use glutin::prelude::*;
+
+    const ROUNDS: u32 = 100;
+
+    pub fn run() {
+        let devices = Device::query_devices().expect("Failed to query devices").collect::<Vec<_>>();
+        let device = devices.first().expect("No available devices");
+        let display =
+            unsafe { Display::with_device(device, None) }.expect("Failed to create display");
+
+        let configs = find_configs(&display);
+        println!("Benchmarking {} configs over {ROUNDS} rounds", configs.len());
+
+        bench("single attribute", &display, |config| config.num_samples() as u32);
+        bench("all attributes", &display, all_attributes);
+    }
+
+    fn bench(name: &str, display: &Display, picker: impl Fn(&Config) -> u32) {
+        let mut uncached = Duration::ZERO;
+        let mut cached = Duration::ZERO;
+        for _ in 0..ROUNDS {
+            // The freshly found configs have nothing cached.
+            let configs = find_configs(display);
+
+            let start = Instant::now();
+            std::hint::black_box(configs.iter().map(&picker).max());
+            uncached += start.elapsed();
+
+            let start = Instant::now();
+            std::hint::black_box(configs.iter().map(&picker).max());
+            cached += start.elapsed();
+        }
+
+        println!("{name}: uncached {:?}, cached {:?}", uncached / ROUNDS, cached / ROUNDS);
+    }
+
+    fn find_configs(display: &Display) -> Vec<Config> {
+        unsafe { display.find_configs(ConfigTemplate::default()) }
+            .expect("Failed to find configs")
+            .collect()
+    }
+
+    fn all_attributes(config: &Config) -> u32 {
+        let mut score = config.num_samples() as u32;
+        score += config.alpha_size() as u32;
+        score += config.depth_size() as u32;
+        score += config.stencil_size() as u32;
+        score += config.srgb_capable() as u32;
+        score += config.hardware_accelerated() as u32;
+        score += config.config_surface_types().bits() as u32;
+        score += config.api().bits() as u32;
+        score += config.swap_interval_range().map_or(0, |range| *range.end());
+        score += config.max_pbuffer_size().map_or(0, |(width, ..)| width);
+        score
+    }
+}
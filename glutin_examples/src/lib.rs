@@ -1,5 +1,5 @@
 use std::error::Error;
-use std::ffi::{CStr, CString};
+use std::ffi::CStr;
 use std::num::NonZeroU32;
 use std::ops::Deref;
 
@@ -19,7 +19,7 @@ use glutin::display::GetGlDisplay;
 use glutin::prelude::*;
 use glutin::surface::{Surface, SwapInterval, WindowSurface};
 
-use glutin_winit::{DisplayBuilder, GlWindow};
+use glutin_winit::{physical_size_to_nonzero, DisplayBuilder, GlWindow};
 
 pub mod gl {
     #![allow(clippy::all)]
@@ -140,18 +140,17 @@ impl ApplicationHandler for App {
         event: WindowEvent,
     ) {
         match event {
-            WindowEvent::Resized(size) if size.width != 0 && size.height != 0 => {
+            WindowEvent::Resized(size) => {
                 // Some platforms like EGL require resizing GL surface to update the size
                 // Notable platforms here are Wayland and macOS, other don't require it
-                // and the function is no-op, but it's wise to resize it for portability
+                // and the function is no-op, which could be checked with
+                // `Surface::needs_resize`, but it's wise to resize it for portability
                 // reasons.
-                if let Some(AppState { gl_surface, window: _ }) = self.state.as_ref() {
+                if let (Some(AppState { gl_surface, window: _ }), Some((width, height))) =
+                    (self.state.as_ref(), physical_size_to_nonzero(size))
+                {
                     let gl_context = self.gl_context.as_ref().unwrap();
-                    gl_surface.resize(
-                        gl_context,
-                        NonZeroU32::new(size.width).unwrap(),
-                        NonZeroU32::new(size.height).unwrap(),
-                    );
+                    gl_surface.resize(gl_context, width, height);
 
                     let renderer = self.renderer.as_ref().unwrap();
                     renderer.resize(size.width as i32, size.height as i32);
@@ -218,17 +217,14 @@ fn create_gl_context(window: &Window, gl_config: &Config) -> NotCurrentContext {
     // has to be created.
     let gl_display = gl_config.display();
 
-    unsafe {
-        gl_display.create_context(gl_config, &context_attributes).unwrap_or_else(|_| {
-            gl_display.create_context(gl_config, &fallback_context_attributes).unwrap_or_else(
-                |_| {
-                    gl_display
-                        .create_context(gl_config, &legacy_context_attributes)
-                        .expect("failed to create context")
-                },
-            )
-        })
-    }
+    let attributes = [context_attributes, fallback_context_attributes, legacy_context_attributes];
+    let (context, _) = unsafe {
+        gl_display
+            .create_context_first_supported(gl_config, &attributes)
+            .expect("failed to create context")
+    };
+
+    context
 }
 
 fn window_attributes() -> WindowAttributes {
@@ -239,7 +235,7 @@ fn window_attributes() -> WindowAttributes {
 
 enum GlDisplayCreationState {
     /// The display was not build yet.
-    Builder(DisplayBuilder),
+    Builder(Box<DisplayBuilder>),
     /// The display was already created for the application.
     Init,
 }
@@ -258,7 +254,7 @@ impl App {
     fn new(template: ConfigTemplateBuilder, display_builder: DisplayBuilder) -> Self {
         Self {
             template,
-            gl_display: GlDisplayCreationState::Builder(display_builder),
+            gl_display: GlDisplayCreationState::Builder(Box::new(display_builder)),
             exit_state: Ok(()),
             gl_context: None,
             state: None,
@@ -301,10 +297,7 @@ pub struct Renderer {
 impl Renderer {
     pub fn new<D: GlDisplay>(gl_display: &D) -> Self {
         unsafe {
-            let gl = gl::Gl::load_with(|symbol| {
-                let symbol = CString::new(symbol).unwrap();
-                gl_display.get_proc_address(symbol.as_c_str()).cast()
-            });
+            let gl = gl_display.load_symbols(|loader| gl::Gl::load_with(loader));
 
             if let Some(renderer) = get_gl_string(&gl, gl::RENDERER) {
                 println!("Running on {}", renderer.to_string_lossy());
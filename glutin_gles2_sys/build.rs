@@ -13,8 +13,14 @@ fn main() {
         println!("cargo:rustc-link-lib=framework=GLKit");
         println!("cargo:rustc-link-lib=framework=OpenGLES");
         let mut file = File::create(dest.join("gles2_bindings.rs")).unwrap();
-        Registry::new(Api::Gles2, (2, 0), Profile::Core, Fallbacks::None, [])
-            .write_bindings(gl_generator::StaticStructGenerator, &mut file)
-            .unwrap();
+        Registry::new(
+            Api::Gles2,
+            (2, 0),
+            Profile::Core,
+            Fallbacks::None,
+            ["GL_OES_depth24", "GL_OES_packed_depth_stencil"],
+        )
+        .write_bindings(gl_generator::StaticStructGenerator, &mut file)
+        .unwrap();
     }
 }
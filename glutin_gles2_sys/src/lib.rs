@@ -35,7 +35,8 @@ pub const kEAGLRenderingAPIOpenGLES3: usize = 3;
 
 extern "C" {
     pub static kEAGLColorFormatRGB565: *const raw::c_void;
-    // pub static kEAGLColorFormatRGBA8: *const raw::c_void;
+    pub static kEAGLColorFormatRGBA8: *const raw::c_void;
+    pub static kEAGLColorFormatSRGBA8: *const raw::c_void;
     pub static kEAGLDrawablePropertyColorFormat: *const raw::c_void;
     pub static kEAGLDrawablePropertyRetainedBacking: *const raw::c_void;
 }
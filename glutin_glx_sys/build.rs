@@ -35,6 +35,8 @@ fn main() {
             "GLX_EXT_framebuffer_sRGB",
             "GLX_EXT_swap_control",
             "GLX_MESA_swap_control",
+            "GLX_NV_swap_group",
+            "GLX_OML_sync_control",
             "GLX_SGI_swap_control",
         ])
         .write_bindings(gl_generator::StructGenerator, &mut file)
@@ -10,6 +10,7 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::redundant_static_lifetimes)]
 #![allow(clippy::unnecessary_cast)]
+#![allow(clippy::too_many_arguments)]
 #![allow(unknown_lints, clippy::missing_transmute_annotations)]
 
 pub use self::glx::types::GLXContext;
@@ -32,6 +32,7 @@ fn main() {
             "WGL_EXT_extensions_string",
             "WGL_EXT_framebuffer_sRGB",
             "WGL_EXT_swap_control",
+            "WGL_NV_swap_group",
         ])
         .write_bindings(gl_generator::StructGenerator, &mut file)
         .unwrap();